@@ -0,0 +1,187 @@
+//! Bulma search select component for [Dioxus](https://dioxuslabs.com), over the
+//! framework-independent state of `search-select-core` (which is re-exported).
+
+use dioxus::prelude::*;
+
+use search_select_core::{
+    interaction::{Effect, Input, Key},
+    SelectSession,
+};
+pub use search_select_core::{SelectDisplay, SelectFilter, SelectState, Selection};
+
+#[derive(Props)]
+pub struct SearchSelectProps<T: 'static> {
+    pub state: SelectState<T>,
+    pub display: SelectDisplay<T>,
+    /// Emitted with the index of a picked option
+    pub on_selected: Option<EventHandler<usize>>,
+    /// Emitted with the index of a selection removed with its tag's delete button
+    pub on_removed: Option<EventHandler<usize>>,
+    /// Hide the selected options from the dropdown
+    #[props(default)]
+    pub omit_selected: bool,
+    #[props(into, default = String::from("Type to search"))]
+    pub placeholder: String,
+}
+
+// This SHOULD be the auto impl, but for some reason that thinks that T needs to be Clone
+impl<T> Clone for SearchSelectProps<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            display: self.display.clone(),
+            on_selected: self.on_selected,
+            on_removed: self.on_removed,
+            omit_selected: self.omit_selected,
+            placeholder: self.placeholder.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for SearchSelectProps<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+            && self.display == other.display
+            && self.on_selected == other.on_selected
+            && self.on_removed == other.on_removed
+            && self.omit_selected == other.omit_selected
+            && self.placeholder == other.placeholder
+    }
+}
+
+/// Bulma search select for Dioxus, over the same [`SelectState`] (and filters)
+/// as the Yew component.
+///
+/// Like the Yew component, picking or removing an option only emits the
+/// callbacks: apply them to the state (`select`/`deselect`) to change the
+/// selection.
+#[allow(non_snake_case)]
+pub fn SearchSelect<T: 'static>(props: SearchSelectProps<T>) -> Element {
+    let mut session =
+        use_signal(|| SelectSession::new(props.state.clone()).omit_selected(props.omit_selected));
+
+    // Bumped when the shared state changes (e.g. from another select), to re-render
+    let revision = use_signal(|| 0usize);
+    let subscription = use_hook(|| {
+        props.state.subscribe(move || {
+            let mut revision = revision;
+            *revision.write() += 1;
+        })
+    });
+    let state = props.state.clone();
+    use_drop(move || state.unsubscribe(subscription));
+    let _ = revision.read();
+
+    let on_selected = props.on_selected;
+    let on_removed = props.on_removed;
+    let mut dispatch = move |input: Input| {
+        let effects = session.write().dispatch(input);
+        for effect in effects {
+            if let (Effect::Select(index), Some(on_selected)) = (effect, on_selected) {
+                on_selected.call(index);
+            }
+        }
+    };
+
+    let session = session.read();
+    let state = session.state();
+    let interaction = session.interaction();
+    let label = |item: &T| props.display.call(item);
+
+    let open = interaction.open;
+    let dropdown_class = if open {
+        "dropdown is-active"
+    } else {
+        "dropdown"
+    };
+    let query = interaction.query.clone();
+    // Tags in multiple mode, while the selection (in single mode) stands in for the placeholder
+    let (tags, placeholder) = if state.is_multiple() {
+        let tags = state
+            .selected_items()
+            .into_iter()
+            .map(|(index, item)| (index, label(item)))
+            .collect();
+        (tags, props.placeholder.clone())
+    } else {
+        let placeholder = state
+            .first_selected()
+            .map(|(_, item)| label(item))
+            .unwrap_or_else(|| props.placeholder.clone());
+        (Vec::new(), placeholder)
+    };
+    let highlight = session.highlighted();
+    let items: Vec<(usize, &'static str, String)> = session
+        .visible()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(position, index)| {
+            let class = if position == highlight {
+                "dropdown-item is-active"
+            } else {
+                "dropdown-item"
+            };
+            Some((index, class, label(state.get(index)?)))
+        })
+        .collect();
+
+    rsx! {
+        div { class: dropdown_class,
+            div { class: "dropdown-trigger",
+                div { class: "tags",
+                    for (index, label) in tags {
+                        span { key: "{index}", class: "tag",
+                            "{label}"
+                            button {
+                                class: "delete is-small",
+                                onclick: move |_| {
+                                    if let Some(on_removed) = on_removed {
+                                        on_removed.call(index);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                input {
+                    class: "input",
+                    r#type: "text",
+                    placeholder: "{placeholder}",
+                    value: "{query}",
+                    oninput: move |event| dispatch(Input::Query(event.value())),
+                    onfocus: move |_| dispatch(Input::Focus),
+                    onblur: move |_| dispatch(Input::Blur),
+                    onkeydown: move |event| {
+                        let (code, key) = (event.code().to_string(), event.key().to_string());
+                        if let Some(key) = Key::from_dom(&code, &key) {
+                            // Home and End only move the highlight while open
+                            if open || !matches!(key, Key::Home | Key::End) {
+                                event.prevent_default();
+                            }
+                            dispatch(Input::Key(key));
+                        }
+                    }
+                }
+            }
+            div { class: "dropdown-menu",
+                div {
+                    class: "dropdown-content",
+                    onmouseleave: move |_| dispatch(Input::Unhover),
+                    for (position, (index, class, label)) in items.into_iter().enumerate() {
+                        a {
+                            key: "{index}",
+                            class: class,
+                            onmousedown: move |event| {
+                                // Keep the focus on the input
+                                event.prevent_default();
+                                dispatch(Input::Pick(position));
+                            },
+                            onmouseenter: move |_| dispatch(Input::Hover(position)),
+                            "{label}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
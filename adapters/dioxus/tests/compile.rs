@@ -0,0 +1,30 @@
+//! Compile check of the component's props (the element is built, never rendered)
+
+use dioxus::prelude::*;
+use dioxus_bulma_search_select::{
+    SearchSelect, SelectDisplay, SelectFilter, SelectState, Selection,
+};
+
+fn fruit() -> SelectState<&'static str> {
+    SelectState::new(
+        vec!["Apple", "Banana", "Cherry"],
+        Selection::none(),
+        SelectFilter::new(|item: &&str, search: &str| item.contains(search)),
+    )
+}
+
+#[test]
+fn props_compile() {
+    let _element = || {
+        rsx! {
+            SearchSelect::<&'static str> {
+                state: fruit(),
+                display: SelectDisplay::new(|item: &&str| item.to_string()),
+                placeholder: "Fruit".to_string(),
+                omit_selected: true,
+                on_selected: move |index: usize| drop(index),
+                on_removed: move |index: usize| drop(index),
+            }
+        }
+    };
+}
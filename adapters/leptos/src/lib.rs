@@ -0,0 +1,169 @@
+//! Bulma search select component for [Leptos](https://leptos.dev), over the
+//! framework-independent state of `search-select-core` (which is re-exported).
+
+use leptos::*;
+
+use search_select_core::{
+    interaction::{Effect, Input, Key},
+    SelectSession,
+};
+pub use search_select_core::{SelectDisplay, SelectFilter, SelectState, Selection};
+
+/// Bulma search select for Leptos, over the same [`SelectState`] (and filters)
+/// as the Yew component.
+///
+/// Like the Yew component, picking or removing an option only emits the
+/// callbacks: apply them to the state (`select`/`deselect`) to change the
+/// selection.
+#[component]
+pub fn SearchSelect<T: 'static>(
+    state: SelectState<T>,
+    display: SelectDisplay<T>,
+    /// Emitted with the index of a picked option
+    #[prop(optional, into)]
+    on_selected: Option<Callback<usize>>,
+    /// Emitted with the index of a selection removed with its tag's delete button
+    #[prop(optional, into)]
+    on_removed: Option<Callback<usize>>,
+    /// Hide the selected options from the dropdown
+    #[prop(optional)]
+    omit_selected: bool,
+    #[prop(into, default = String::from("Type to search"))] placeholder: String,
+) -> impl IntoView {
+    let session = create_rw_signal(SelectSession::new(state.clone()).omit_selected(omit_selected));
+    let display = store_value(display);
+
+    // Re-render when the shared state changes (e.g. from another select)
+    let subscription = state.subscribe(move || session.update(|_| ()));
+    on_cleanup(move || state.unsubscribe(subscription));
+
+    let dispatch = move |input: Input| {
+        let effects = session
+            .try_update(|session| session.dispatch(input))
+            .unwrap_or_default();
+        for effect in effects {
+            if let (Effect::Select(index), Some(on_selected)) = (effect, on_selected) {
+                on_selected.call(index);
+            }
+        }
+    };
+
+    let label = move |index: usize| {
+        session.with(|session| {
+            session
+                .state()
+                .get(index)
+                .map(|item| display.with_value(|display| display.call(item)))
+                .unwrap_or_default()
+        })
+    };
+
+    // Tags in multiple mode
+    let tags = move || -> Vec<usize> {
+        session.with(|session| {
+            if session.state().is_multiple() {
+                session
+                    .state()
+                    .selected_items()
+                    .into_iter()
+                    .map(|(index, _)| index)
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        })
+    };
+
+    // The selection (in single mode) stands in for the placeholder
+    let placeholder = move || {
+        session
+            .with(|session| {
+                if session.state().is_multiple() {
+                    None
+                } else {
+                    session.state().first_selected().map(|(index, _)| index)
+                }
+            })
+            .map(label)
+            .unwrap_or_else(|| placeholder.clone())
+    };
+
+    view! {
+        <div
+            class="dropdown"
+            class:is-active=move || session.with(|session| session.interaction().open)
+        >
+            <div class="dropdown-trigger">
+                <div class="tags">
+                    {move || {
+                        tags()
+                            .into_iter()
+                            .map(|index| {
+                                view! {
+                                    <span class="tag">
+                                        {label(index)}
+                                        <button
+                                            class="delete is-small"
+                                            on:click=move |_| {
+                                                if let Some(on_removed) = on_removed {
+                                                    on_removed.call(index);
+                                                }
+                                            }
+                                        ></button>
+                                    </span>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+                <input
+                    class="input"
+                    type="text"
+                    placeholder=placeholder
+                    prop:value=move || session.with(|session| session.interaction().query.clone())
+                    on:input=move |event| dispatch(Input::Query(event_target_value(&event)))
+                    on:focus=move |_| dispatch(Input::Focus)
+                    on:blur=move |_| dispatch(Input::Blur)
+                    on:keydown=move |event: ev::KeyboardEvent| {
+                        if let Some(key) = Key::from_dom(&event.code(), &event.key()) {
+                            // Home and End only move the highlight while open
+                            let open = session.with(|session| session.interaction().open);
+                            if open || !matches!(key, Key::Home | Key::End) {
+                                event.prevent_default();
+                            }
+                            dispatch(Input::Key(key));
+                        }
+                    }
+                />
+            </div>
+            <div class="dropdown-menu">
+                <div class="dropdown-content" on:mouseleave=move |_| dispatch(Input::Unhover)>
+                    {move || {
+                        let (visible, highlight) =
+                            session.with(|session| (session.visible(), session.highlighted()));
+                        visible
+                            .into_iter()
+                            .enumerate()
+                            .map(|(position, index)| {
+                                view! {
+                                    <a
+                                        class="dropdown-item"
+                                        class:is-active=position == highlight
+                                        // Keep the focus on the input
+                                        on:mousedown=move |event| {
+                                            event.prevent_default();
+                                            dispatch(Input::Pick(position));
+                                        }
+                                        on:mouseenter=move |_| dispatch(Input::Hover(position))
+                                    >
+                                        {label(index)}
+                                    </a>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
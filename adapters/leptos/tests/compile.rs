@@ -0,0 +1,30 @@
+//! Compile check of the component's props (the view is built, never mounted)
+
+use leptos::*;
+use leptos_bulma_search_select::{
+    SearchSelect, SelectDisplay, SelectFilter, SelectState, Selection,
+};
+
+fn fruit() -> SelectState<&'static str> {
+    SelectState::new(
+        vec!["Apple", "Banana", "Cherry"],
+        Selection::none(),
+        SelectFilter::new(|item: &&str, search: &str| item.contains(search)),
+    )
+}
+
+#[test]
+fn props_compile() {
+    let _view = || {
+        view! {
+            <SearchSelect
+                state=fruit()
+                display=SelectDisplay::new(|item: &&str| item.to_string())
+                placeholder="Fruit".to_string()
+                omit_selected=true
+                on_selected=|index: usize| drop(index)
+                on_removed=|index: usize| drop(index)
+            />
+        }
+    };
+}
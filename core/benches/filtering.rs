@@ -0,0 +1,64 @@
+//! Filter throughput and `filtered_items()` cost at growing option counts.
+//! Run with `cargo bench -p search-select-core`
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use futures::executor::block_on;
+
+use search_select_core::{SelectFilter, SelectKeyFilter, SelectState, Selection};
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// `n` numbered options, with a case-insensitive substring filter
+fn state(n: usize) -> SelectState<String> {
+    let options = (0..n).map(|i| format!("Option {}", i)).collect::<Vec<_>>();
+    SelectState::new(
+        options,
+        Selection::empty(),
+        SelectFilter::new(|item: &String, search: &str| {
+            item.to_lowercase().contains(&search.to_lowercase())
+        }),
+    )
+}
+
+fn filter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filter");
+    for &n in SIZES.iter() {
+        group.throughput(Throughput::Elements(n as u64));
+
+        let state = state(n);
+        group.bench_with_input(BenchmarkId::new("filter", n), &state, |b, state| {
+            b.iter(|| block_on(state.filter(black_box("42"))))
+        });
+
+        // Precomputed lowercase keys instead of calling the filter for each option
+        let keyed =
+            state.with_search_key(|item: &String| item.clone(), SelectKeyFilter::contains());
+        group.bench_with_input(BenchmarkId::new("search_key", n), &keyed, |b, state| {
+            b.iter(|| block_on(state.filter(black_box("42"))))
+        });
+    }
+    group.finish();
+}
+
+fn filtered_items(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filtered_items");
+    for &n in SIZES.iter() {
+        group.throughput(Throughput::Elements(n as u64));
+
+        // Every tenth option selected, so the selection lookups aren't trivial
+        let state = state(n);
+        state.select_many(&(0..n).step_by(10).collect::<Vec<_>>());
+        group.bench_with_input(BenchmarkId::new("unfiltered", n), &state, |b, state| {
+            b.iter(|| state.filtered_items().len())
+        });
+
+        block_on(state.filter("1"));
+        group.bench_with_input(BenchmarkId::new("filtered", n), &state, |b, state| {
+            b.iter(|| state.filtered_items().len())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, filter, filtered_items);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+use std::fmt::Write;
+
+/// Plain data dump of a [`SelectState`](crate::SelectState), from
+/// `SelectState::debug_snapshot`, to attach to bug reports (see `to_json`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectDebug {
+    /// Number of options
+    pub options: usize,
+    /// Number of options matching the query (and predicate)
+    pub filtered: usize,
+    /// Selection variant: `"AlwaysOne"`, `"MaybeOne"` or `"Multiple"` (`"Poisoned"`
+    /// if its lock was poisoned)
+    pub mode: &'static str,
+    /// Selected indices, in ascending order
+    pub selected: Vec<usize>,
+    /// Selected indices which don't point at an option
+    pub invalid: Vec<usize>,
+    pub query: Option<String>,
+    pub pristine: bool,
+    pub generation: usize,
+    pub filter_revision: usize,
+    /// Number of layout entries (headers, dividers, ...), if the state has a layout
+    pub layout: Option<usize>,
+    /// Number of options with metadata (see `SelectState::set_meta`)
+    pub meta: usize,
+    pub has_search_key: bool,
+    pub has_predicate: bool,
+}
+
+impl SelectDebug {
+    /// The snapshot as a JSON object
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        let _ = write!(json, "\"options\":{}", self.options);
+        let _ = write!(json, ",\"filtered\":{}", self.filtered);
+        let _ = write!(json, ",\"mode\":\"{}\"", self.mode);
+        let _ = write!(json, ",\"selected\":{}", json_indices(&self.selected));
+        let _ = write!(json, ",\"invalid\":{}", json_indices(&self.invalid));
+        json.push_str(",\"query\":");
+        match self.query {
+            Some(ref query) => json_string(&mut json, query),
+            None => json.push_str("null"),
+        }
+        let _ = write!(json, ",\"pristine\":{}", self.pristine);
+        let _ = write!(json, ",\"generation\":{}", self.generation);
+        let _ = write!(json, ",\"filter_revision\":{}", self.filter_revision);
+        match self.layout {
+            Some(entries) => {
+                let _ = write!(json, ",\"layout\":{}", entries);
+            }
+            None => json.push_str(",\"layout\":null"),
+        }
+        let _ = write!(json, ",\"meta\":{}", self.meta);
+        let _ = write!(json, ",\"has_search_key\":{}", self.has_search_key);
+        let _ = write!(json, ",\"has_predicate\":{}", self.has_predicate);
+        json.push('}');
+        json
+    }
+}
+
+fn json_indices(indices: &[usize]) -> String {
+    let indices = indices
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<_>>();
+    format!("[{}]", indices.join(","))
+}
+
+fn json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
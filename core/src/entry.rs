@@ -0,0 +1,72 @@
+use crate::{SelectAction, SelectFilter, SelectState, Selection};
+
+/// An entry of a structured option list, so headers, dividers and actions
+/// (e.g. "Manage…") can live in the data alongside the options.
+///
+/// ```ignore
+/// let state = SelectState::from_entries(
+///     vec![
+///         OptionEntry::Header("Fruit".into()),
+///         OptionEntry::Item("Apple".to_string()),
+///         OptionEntry::Item("Banana".to_string()),
+///         OptionEntry::Divider,
+///         OptionEntry::action("Manage…", || open_settings()),
+///     ],
+///     Selection::none(),
+///     filter,
+/// );
+/// ```
+#[derive(Clone, PartialEq)]
+pub enum OptionEntry<T> {
+    /// A selectable option
+    Item(T),
+    /// A non-interactive heading above the following options
+    Header(String),
+    /// A non-interactive divider
+    Divider,
+    /// A row which runs `callback` when picked, without changing the selection
+    Action {
+        label: String,
+        callback: SelectAction,
+    },
+}
+
+impl<T> OptionEntry<T> {
+    pub fn action<S: Into<String>, A: Into<SelectAction>>(label: S, callback: A) -> Self {
+        OptionEntry::Action {
+            label: label.into(),
+            callback: callback.into(),
+        }
+    }
+}
+
+impl<T> SelectState<T> {
+    /// Create a state from a structured list. The options are the `Item` entries
+    /// (indexed in order, skipping the other entries), while headers, dividers
+    /// and actions are rendered around them in the dropdown.
+    ///
+    /// The structure is dropped if the options are replaced.
+    pub fn from_entries<I: IntoIterator<Item = OptionEntry<T>>, F: Into<SelectFilter<T>>>(
+        entries: I,
+        selection: Selection,
+        filter_fn: F,
+    ) -> Self {
+        let mut options = Vec::new();
+        let layout = entries
+            .into_iter()
+            .map(|entry| match entry {
+                OptionEntry::Item(item) => {
+                    options.push(item);
+                    OptionEntry::Item(options.len() - 1)
+                }
+                OptionEntry::Header(label) => OptionEntry::Header(label),
+                OptionEntry::Divider => OptionEntry::Divider,
+                OptionEntry::Action { label, callback } => OptionEntry::Action { label, callback },
+            })
+            .collect::<Vec<_>>();
+
+        let mut state = Self::new(options, selection, filter_fn);
+        state.layout = Some(layout.into());
+        state
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+use crate::{SelectDisplay, SelectFilter, SelectState, Selection};
+
+/// A fieldless enum (or other fixed set of values) usable directly as options.
+///
+/// Implement this by listing the variants, or enable the `strum` feature to get
+/// it for any enum deriving `strum::VariantArray`:
+///
+/// ```ignore
+/// impl SelectEnum for Fruit {
+///     const VARIANTS: &'static [Self] = &[Fruit::Apple, Fruit::Banana];
+/// }
+///
+/// let state = SelectState::<Fruit>::from_enum(Selection::none());
+/// ```
+pub trait SelectEnum: Display + Sized + 'static {
+    const VARIANTS: &'static [Self];
+}
+
+#[cfg(feature = "strum")]
+impl<T: strum::VariantArray + Display + 'static> SelectEnum for T {
+    const VARIANTS: &'static [Self] = <T as strum::VariantArray>::VARIANTS;
+}
+
+impl<T: SelectEnum> SelectState<T> {
+    /// Create a state over all variants, filtered by a case-insensitive match on
+    /// their `Display` output
+    pub fn from_enum(selection: Selection) -> Self {
+        Self::from_static(T::VARIANTS, selection, SelectFilter::display_contains())
+    }
+
+    /// The selected variant (the first, in multiple mode)
+    pub fn selected_variant(&self) -> Option<&T> {
+        self.first_selected().map(|(_, item)| item)
+    }
+
+    /// All selected variants
+    pub fn selected_variants(&self) -> Vec<&T> {
+        self.selected_items()
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect()
+    }
+}
+
+impl<T: Display> SelectFilter<T> {
+    /// Case-insensitive substring match on the item's `Display` output
+    pub fn display_contains() -> Self {
+        SelectFilter::new(|item: &T, search: &str| {
+            item.to_string()
+                .to_lowercase()
+                .contains(&search.to_lowercase())
+        })
+    }
+}
+
+impl<T: Display> SelectDisplay<T> {
+    /// Display items using their `Display` implementation
+    pub fn from_display() -> Self {
+        SelectDisplay::new(|item: &T| item.to_string())
+    }
+}
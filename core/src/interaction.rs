@@ -0,0 +1,280 @@
+//! Framework-independent interaction logic of the select, as a pure reducer.
+//!
+//! [`reduce`] takes the interaction state (open, highlight, search text) and an
+//! input, and returns the new state along with the effects (callbacks to emit,
+//! searches to run) for the caller to carry out. The caller owns the options
+//! and selection, and describes the current dropdown rows to the reducer.
+//!
+//! [`SelectSession`](crate::SelectSession) drives it for the Leptos and Dioxus
+//! components and the headless `SelectHarness`. The Yew `Select` has far more
+//! interactions than the reducer models (tags, groups, ranges, actions), but
+//! runs its highlight changes (arrow keys, Home/End and the pointer) through
+//! [`reduce`] too, and opens on the same row ([`Menu::opening_highlight`]).
+
+/// Which highlight the Enter key commits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HighlightSource {
+    /// The row highlighted with the arrow keys (pointer hover is ignored)
+    Keyboard,
+    /// The row under the pointer if any, otherwise the keyboard highlight
+    Pointer,
+}
+
+impl Default for HighlightSource {
+    fn default() -> Self {
+        HighlightSource::Keyboard
+    }
+}
+
+/// Interaction state of a select, independent of its options and selection
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Interaction {
+    /// Whether the dropdown is open
+    pub open: bool,
+    /// Position of the row highlighted with the keyboard
+    pub highlight: usize,
+    /// Position of the row under the pointer, if any
+    pub hover: Option<usize>,
+    /// Search text
+    pub query: String,
+}
+
+impl Interaction {
+    /// Position of the row shown highlighted, which Enter commits
+    pub fn highlighted(&self, enter_commits: HighlightSource) -> usize {
+        match (enter_commits, self.hover) {
+            (HighlightSource::Pointer, Some(position)) => position,
+            _ => self.highlight,
+        }
+    }
+}
+
+/// The dropdown as the caller currently shows it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Menu<'a> {
+    /// The rows, in display order
+    pub rows: &'a [RowKind],
+    /// Position of the selected row, highlighted when the dropdown opens (so
+    /// Enter doesn't re-select the first option). Only set in single mode.
+    pub selected: Option<usize>,
+    pub enter_commits: HighlightSource,
+}
+
+impl<'a> Menu<'a> {
+    /// `rows` without a selected row, committing the keyboard highlight
+    pub fn new(rows: &'a [RowKind]) -> Self {
+        Self {
+            rows,
+            selected: None,
+            enter_commits: HighlightSource::default(),
+        }
+    }
+
+    /// Position highlighted when the dropdown opens: the selected row, or else
+    /// the first navigable one
+    pub fn opening_highlight(&self) -> usize {
+        self.selected
+            .filter(|&position| {
+                self.rows
+                    .get(position)
+                    .map_or(false, |row| row.is_navigable())
+            })
+            .unwrap_or_else(|| first_navigable(self.rows))
+    }
+}
+
+/// What a dropdown row is, as far as the interaction logic is concerned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowKind {
+    /// An option (global index)
+    Option(usize),
+    /// Any other row which can be highlighted and picked (e.g. an action)
+    Other,
+    /// A row the highlight skips (e.g. a header or divider)
+    Inert,
+}
+
+impl RowKind {
+    pub fn is_navigable(self) -> bool {
+        !matches!(self, RowKind::Inert)
+    }
+}
+
+/// Keys handled by the select's `onkeydown` handler
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Enter,
+    Escape,
+    ArrowUp,
+    ArrowDown,
+    /// Highlights the first row (while open)
+    Home,
+    /// Highlights the last row (while open), e.g. a "Manage…" action
+    End,
+}
+
+impl Key {
+    /// The key for a DOM `KeyboardEvent`'s `code` and `key`, if the select
+    /// handles it. Like the Yew `Select`, this goes by the physical key
+    /// (`code`), except for virtual keyboards, which report their action key
+    /// without a `code`.
+    pub fn from_dom(code: &str, key: &str) -> Option<Self> {
+        match code {
+            "" if key == "Enter" => Some(Key::Enter),
+            "Enter" | "NumpadEnter" => Some(Key::Enter),
+            "Escape" => Some(Key::Escape),
+            "ArrowUp" => Some(Key::ArrowUp),
+            "ArrowDown" => Some(Key::ArrowDown),
+            "Home" => Some(Key::Home),
+            "End" => Some(Key::End),
+            _ => None,
+        }
+    }
+}
+
+/// Something that happened to the select
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    Focus,
+    Blur,
+    /// The search text was replaced (e.g. typed)
+    Query(String),
+    Key(Key),
+    /// The pointer moved over the row at this position
+    Hover(usize),
+    /// The pointer left the dropdown
+    Unhover,
+    /// The row at this position was clicked
+    Pick(usize),
+}
+
+/// Something the caller has to do after an input
+#[derive(Clone, Debug, PartialEq)]
+pub enum Effect {
+    /// Emit `onselected` for this option
+    Select(usize),
+    /// Run the row at this position which isn't an option (e.g. an action)
+    Activate(usize),
+    /// Filter the options by this search text (an empty one clears the search)
+    Search(String),
+}
+
+/// Apply `input` to `state`, given the dropdown currently shown
+pub fn reduce(mut state: Interaction, input: Input, menu: &Menu) -> (Interaction, Vec<Effect>) {
+    let rows = menu.rows;
+    let mut effects = Vec::new();
+    match input {
+        Input::Focus => open(&mut state, menu),
+        Input::Blur | Input::Key(Key::Escape) => close(&mut state, &mut effects),
+        Input::Query(query) => {
+            state.open = true;
+            state.query = query.clone();
+            effects.push(Effect::Search(query));
+        }
+        Input::Key(Key::Enter) => {
+            let highlighted = state.highlighted(menu.enter_commits);
+            pick(&mut state, highlighted, rows, &mut effects);
+        }
+        // Keys move on from the row shown highlighted, taking over from the pointer
+        Input::Key(Key::ArrowUp) if state.open => {
+            state.highlight = step(rows, state.highlighted(menu.enter_commits), false);
+            state.hover = None;
+        }
+        Input::Key(Key::ArrowDown) if state.open => {
+            state.highlight = step(rows, state.highlighted(menu.enter_commits), true);
+            state.hover = None;
+        }
+        Input::Key(Key::ArrowUp) | Input::Key(Key::ArrowDown) => open(&mut state, menu),
+        Input::Key(Key::Home) if state.open => {
+            state.highlight = first_navigable(rows);
+            state.hover = None;
+        }
+        Input::Key(Key::End) if state.open => {
+            state.highlight = last_navigable(rows);
+            state.hover = None;
+        }
+        // Closed, they move the caret in the input instead
+        Input::Key(Key::Home) | Input::Key(Key::End) => {}
+        Input::Hover(position) => state.hover = Some(position),
+        Input::Unhover => state.hover = None,
+        Input::Pick(position) => pick(&mut state, position, rows, &mut effects),
+    }
+    (state, effects)
+}
+
+/// Position of the next navigable row after (or before) `position`, staying
+/// put if there is none
+pub fn step(rows: &[RowKind], position: usize, forward: bool) -> usize {
+    let found = if forward {
+        rows.iter()
+            .enumerate()
+            .skip(position + 1)
+            .find(|(_, row)| row.is_navigable())
+    } else {
+        rows.iter()
+            .enumerate()
+            .take(position.min(rows.len()))
+            .rev()
+            .find(|(_, row)| row.is_navigable())
+    };
+    found.map_or(position, |(i, _)| i)
+}
+
+/// `position` if its row is navigable, or else the closest navigable row
+/// (preferring earlier ones), e.g. after the rows were filtered
+pub fn clamp(rows: &[RowKind], position: usize) -> usize {
+    if rows.get(position).map_or(false, |row| row.is_navigable()) {
+        return position;
+    }
+    let before = rows
+        .iter()
+        .take(position.min(rows.len()))
+        .rposition(|row| row.is_navigable());
+    let after = rows
+        .iter()
+        .skip(position)
+        .position(|row| row.is_navigable())
+        .map(|i| position + i);
+    before.or(after).unwrap_or(0)
+}
+
+/// Position of the first navigable row (0 if there is none)
+pub fn first_navigable(rows: &[RowKind]) -> usize {
+    rows.iter().position(|row| row.is_navigable()).unwrap_or(0)
+}
+
+/// Position of the last navigable row (0 if there is none)
+pub fn last_navigable(rows: &[RowKind]) -> usize {
+    rows.iter().rposition(|row| row.is_navigable()).unwrap_or(0)
+}
+
+fn open(state: &mut Interaction, menu: &Menu) {
+    if !state.open {
+        state.open = true;
+        state.highlight = menu.opening_highlight();
+    }
+}
+
+fn close(state: &mut Interaction, effects: &mut Vec<Effect>) {
+    state.open = false;
+    state.highlight = 0;
+    state.hover = None;
+    if !state.query.is_empty() {
+        state.query.clear();
+        effects.push(Effect::Search(String::new()));
+    }
+}
+
+fn pick(state: &mut Interaction, position: usize, rows: &[RowKind], effects: &mut Vec<Effect>) {
+    match rows.get(position) {
+        Some(&RowKind::Option(index)) => {
+            effects.push(Effect::Select(index));
+            close(state, effects);
+        }
+        Some(RowKind::Other) => {
+            effects.push(Effect::Activate(position));
+            close(state, effects);
+        }
+        Some(RowKind::Inert) | None => {}
+    }
+}
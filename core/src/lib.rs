@@ -0,0 +1,37 @@
+//! Framework-independent state of a search select: the options and their
+//! [`Selection`], the [`SelectState`] shared between components, the filter and
+//! display wrappers, and the [`interaction`] reducer.
+//!
+//! This has no dependency on Yew (except for the callback helpers behind the
+//! `yew` feature), so it can be tested natively and reused from other
+//! frameworks. `yew-bulma-search-select` re-exports all of it, and the
+//! `leptos-bulma-search-select` and `dioxus-bulma-search-select` crates (under
+//! `adapters/`) build a `SearchSelect` component for those frameworks on it.
+
+mod debug;
+pub use debug::SelectDebug;
+mod entry;
+pub use entry::OptionEntry;
+mod enums;
+pub use enums::SelectEnum;
+pub mod interaction;
+#[doc(hidden)]
+pub mod log;
+mod meta;
+pub use meta::OptionMeta;
+mod options;
+mod session;
+pub use session::SelectSession;
+mod state;
+pub use state::{ReselectionReport, SelectState};
+mod selection;
+pub use selection::Selection;
+mod wrappers;
+pub use wrappers::{
+    EmptyState, SelectAction, SelectBadge, SelectDisplay, SelectFilter, SelectFilterWithCtx,
+    SelectGroup, SelectImage, SelectKeyFilter, SelectOrder, SelectPredicate, SelectSearchKey,
+    SelectValue,
+};
+
+#[cfg(feature = "presets")]
+pub mod presets;
@@ -0,0 +1,30 @@
+//! Debug instrumentation. Events are only emitted (via `tracing`) when the
+//! `log` feature is enabled, otherwise the macros compile to nothing.
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        tracing::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            // Keep the arguments "used" so disabling the feature doesn't cause warnings
+            let _ = format_args!($($arg)*);
+        }
+    };
+}
+pub(crate) use debug;
+
+/// Current timestamp in milliseconds, for measuring durations
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Current timestamp in milliseconds, for measuring durations
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
+}
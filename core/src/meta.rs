@@ -0,0 +1,20 @@
+/// Runtime annotations for one option, set with `SelectState::set_meta`
+/// (without rebuilding the options).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptionMeta {
+    /// Shown, but can't be highlighted or picked
+    pub disabled: bool,
+    /// Listed before the other matching options
+    pub pinned: bool,
+    /// Text of a tag shown at the end of the option's row
+    pub badge: Option<String>,
+    /// Group to list the option under, instead of the one from `group_by`
+    pub group: Option<String>,
+}
+
+impl OptionMeta {
+    /// No annotations at all (the same as not having metadata)
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,51 @@
+use std::{ops::Deref, sync::Arc};
+
+/// Storage for the option set: either shared on the heap, or a static slice
+/// (e.g. a constant table) which doesn't need to be allocated or copied.
+pub enum Options<T: 'static> {
+    Shared(Arc<[T]>),
+    Static(&'static [T]),
+}
+
+impl<T> Options<T> {
+    /// Whether both refer to the same underlying slice
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Options::Shared(a), Options::Shared(b)) => Arc::ptr_eq(a, b),
+            (Options::Static(a), Options::Static(b)) => std::ptr::eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for Options<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Options::Shared(options) => Options::Shared(options.clone()),
+            Options::Static(options) => Options::Static(options),
+        }
+    }
+}
+
+impl<T> Deref for Options<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Options::Shared(options) => options,
+            Options::Static(options) => options,
+        }
+    }
+}
+
+impl<T> From<Arc<[T]>> for Options<T> {
+    fn from(options: Arc<[T]>) -> Self {
+        Options::Shared(options)
+    }
+}
+
+impl<T> From<&'static [T]> for Options<T> {
+    fn from(options: &'static [T]) -> Self {
+        Options::Static(options)
+    }
+}
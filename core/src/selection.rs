@@ -3,7 +3,7 @@ use std::collections::BTreeSet;
 // TODO: evaluate performance of using btreemap's instead of sets (it's nice to have the sortedness, but performance?)
 // insertion should (almost always) be a greater value?
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Selection {
     AlwaysOne(usize),
     MaybeOne(Option<usize>),
@@ -39,6 +39,27 @@ impl Selection {
         Selection::Multiple(indices.into_iter().collect::<BTreeSet<usize>>())
     }
 
+    /// Create a new `Selection::MaybeOne` selecting the first option equal to
+    /// `value` (or nothing if there is none)
+    pub fn some_value<T: PartialEq>(options: &[T], value: &T) -> Self {
+        Selection::MaybeOne(options.iter().position(|option| option == value))
+    }
+
+    /// Create a new `Selection::Multiple` selecting the first option equal to
+    /// each of `values` (e.g. a `Vec` or `HashSet`). Values without an equal
+    /// option are skipped.
+    pub fn multiple_values<'a, T, I>(options: &[T], values: I) -> Self
+    where
+        T: PartialEq + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        Selection::multiple(
+            values
+                .into_iter()
+                .filter_map(|value| options.iter().position(|option| option == value)),
+        )
+    }
+
     pub fn len(&self) -> usize {
         match self {
             Selection::MaybeOne(None) => 0,
@@ -0,0 +1,139 @@
+use crate::{
+    interaction::{clamp, reduce, Effect, HighlightSource, Input, Interaction, Menu, RowKind},
+    SelectState,
+};
+
+/// One select's view of a (shared) [`SelectState`]: its interaction state and
+/// search results, driven through [`reduce`].
+///
+/// This is the glue each framework's select component needs on top of the
+/// reducer. The session carries out the searches itself, and hands the other
+/// effects (e.g. emitting `onselected`) back to the component.
+pub struct SelectSession<T: 'static> {
+    state: SelectState<T>,
+    omit_selected: bool,
+    enter_commits: HighlightSource,
+
+    interaction: Interaction,
+    /// Global indices matching the search text, kept per session like the state's query
+    filtered: Option<Vec<usize>>,
+}
+
+impl<T> SelectSession<T> {
+    pub fn new(state: SelectState<T>) -> Self {
+        Self {
+            state,
+            omit_selected: false,
+            enter_commits: HighlightSource::default(),
+
+            interaction: Interaction::default(),
+            filtered: None,
+        }
+    }
+
+    /// Hide the selected options from the dropdown
+    pub fn omit_selected(mut self, omit_selected: bool) -> Self {
+        self.omit_selected = omit_selected;
+        self
+    }
+
+    /// Which highlight the Enter key commits
+    pub fn enter_commits(mut self, enter_commits: HighlightSource) -> Self {
+        self.enter_commits = enter_commits;
+        self
+    }
+
+    pub fn state(&self) -> &SelectState<T> {
+        &self.state
+    }
+
+    pub fn interaction(&self) -> &Interaction {
+        &self.interaction
+    }
+
+    /// Position of the row shown highlighted, which Enter commits
+    pub fn highlighted(&self) -> usize {
+        self.interaction.highlighted(self.enter_commits)
+    }
+
+    /// Global indices of the options shown in the dropdown
+    pub fn visible(&self) -> Vec<usize> {
+        let items = match self.filtered {
+            Some(ref indices) => self
+                .state
+                .with_selected(self.state.allowed(indices.iter().copied())),
+            None => self
+                .state
+                .with_selected(self.state.allowed(0..self.state.options.len())),
+        };
+        let mut items = items
+            .into_iter()
+            .filter(|(_, selected)| !(self.omit_selected && *selected))
+            .collect::<Vec<_>>();
+        self.state.pinned_first(&mut items);
+        items.into_iter().map(|(i, _)| i).collect()
+    }
+
+    /// The dropdown rows, as described to the reducer (disabled options are inert)
+    pub fn rows(&self) -> Vec<RowKind> {
+        self.visible()
+            .into_iter()
+            .map(|index| {
+                if self.state.is_disabled(index) {
+                    RowKind::Inert
+                } else {
+                    RowKind::Option(index)
+                }
+            })
+            .collect()
+    }
+
+    /// Run `input` through the reducer, returning the effects left for the
+    /// component to carry out (anything but searches)
+    pub fn dispatch(&mut self, input: Input) -> Vec<Effect> {
+        let rows = self.rows();
+        // Like the Yew `Select`, only a single selection is highlighted on opening
+        let selected = if self.state.is_multiple() {
+            None
+        } else {
+            rows.iter().position(
+                |row| matches!(*row, RowKind::Option(index) if self.state.is_selected(index)),
+            )
+        };
+        let menu = Menu {
+            rows: &rows,
+            selected,
+            enter_commits: self.enter_commits,
+        };
+        let (interaction, effects) = reduce(std::mem::take(&mut self.interaction), input, &menu);
+        self.interaction = interaction;
+
+        let mut searched = false;
+        let effects = effects
+            .into_iter()
+            .filter(|effect| match effect {
+                Effect::Search(query) => {
+                    self.filtered = if query.is_empty() {
+                        None
+                    } else {
+                        Some(self.state.matching_indices(query))
+                    };
+                    searched = true;
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+        if searched {
+            // Keep the highlight on a row that is still shown
+            self.interaction.highlight = clamp(&self.rows(), self.interaction.highlight);
+        }
+        effects
+    }
+
+    /// Clear the search text, without closing the dropdown
+    pub fn clear_search(&mut self) {
+        self.filtered = None;
+        self.interaction.query.clear();
+    }
+}
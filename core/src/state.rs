@@ -0,0 +1,1179 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap},
+    hash::Hash,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+#[cfg(feature = "yew")]
+use yew::Callback;
+
+use crate::{
+    log::{debug, now},
+    options::Options,
+    OptionEntry, OptionMeta, SelectDebug, SelectDisplay, SelectFilter, SelectKeyFilter,
+    SelectOrder, SelectPredicate, SelectSearchKey, Selection,
+};
+
+/// Outcome of `SelectState::replace_options_reselecting`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReselectionReport {
+    /// New indices of the selections found in the new options
+    pub kept: Vec<usize>,
+    /// Old indices of the selections which weren't found, and were dropped
+    pub dropped: Vec<usize>,
+    /// New index of each old option (`None` if it wasn't found), for callers
+    /// tracking indices outside of the state. Only filled in by the keyed
+    /// variants (`replace_options_reselecting_by_key` and `_by_display`): with a
+    /// `selection_eq` closure, each lookup scans the new options, so only the
+    /// selected options are looked up and this is left empty.
+    pub mapping: Vec<Option<usize>>,
+}
+
+impl ReselectionReport {
+    /// New index of the old option at `index`, if it was found (see `mapping`)
+    pub fn translate(&self, index: usize) -> Option<usize> {
+        self.mapping.get(index).copied().flatten()
+    }
+}
+
+#[derive(Debug)]
+pub enum Filtered {
+    None,
+    /// Global indices of the matching options, in ascending order
+    Some(Vec<usize>),
+    All,
+}
+
+/// Internal state is wrapped in an Arc, so cloning this is not very expensive
+///
+/// Options don't need to implement `Clone`: they are stored in an `Arc<[T]>`, so
+/// the same option set can be shared between several states by passing in a
+/// clone of the `Arc` (rather than copying the items).
+///
+/// Several `Select`s can be attached to the same state (e.g. a compact picker
+/// and a full one for the same field). Each component subscribes to changes, so
+/// a selection made in one is shown in all of them, while the search query,
+/// filtered list and highlight stay local to each component.
+pub struct SelectState<T: 'static> {
+    #[doc(hidden)]
+    pub options: Options<T>,
+    /// Structure (headers, dividers, actions) around the options, by option index
+    #[doc(hidden)]
+    pub layout: Option<Arc<[OptionEntry<usize>]>>,
+    pub(crate) selected_indices: Arc<RwLock<Selection>>,
+    pub(crate) filtered_indices: Arc<RwLock<Filtered>>,
+
+    filter_fn: SelectFilter<T>,
+    filter_input: Arc<RwLock<Option<String>>>,
+
+    search_key: Option<(SelectSearchKey<T>, SelectKeyFilter)>,
+    search_keys: Option<Arc<[String]>>,
+    /// Restriction applied on top of the search (see `set_predicate`)
+    predicate: Arc<RwLock<Option<SelectPredicate<T>>>>,
+    /// Bumped by `refresh_filter`, so components know to re-run their searches
+    filter_revision: Arc<AtomicUsize>,
+    /// Annotations set with `set_meta`, by option index
+    meta: Arc<RwLock<HashMap<usize, OptionMeta>>>,
+    /// Bumped whenever `meta` changes, so components know to regroup
+    meta_revision: Arc<AtomicUsize>,
+
+    /// Bumped whenever the filtered set or selection changes, so renders can be skipped
+    generation: Arc<AtomicUsize>,
+    /// True until a selection is explicitly made (or after the selection is reset)
+    pristine: Arc<AtomicBool>,
+
+    /// Listeners called after a mutation through any handle to this state.
+    /// They are usually tied to a component (and so to its thread), hence `Rc`.
+    subscribers: Rc<RefCell<Vec<(usize, Subscriber)>>>,
+    next_subscriber: Rc<Cell<usize>>,
+}
+
+/// Listener added with [`SelectState::subscribe`]
+type Subscriber = Rc<dyn Fn()>;
+
+impl<T> Clone for SelectState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            options: self.options.clone(),
+            layout: self.layout.clone(),
+            selected_indices: self.selected_indices.clone(),
+            filtered_indices: self.filtered_indices.clone(),
+            filter_fn: self.filter_fn.clone(),
+            filter_input: self.filter_input.clone(),
+            search_key: self.search_key.clone(),
+            search_keys: self.search_keys.clone(),
+            predicate: self.predicate.clone(),
+            filter_revision: self.filter_revision.clone(),
+            meta: self.meta.clone(),
+            meta_revision: self.meta_revision.clone(),
+            generation: self.generation.clone(),
+            pristine: self.pristine.clone(),
+            subscribers: self.subscribers.clone(),
+            next_subscriber: self.next_subscriber.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for SelectState<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.options.ptr_eq(&other.options)
+            && match (&self.layout, &other.layout) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && Arc::ptr_eq(&self.selected_indices, &other.selected_indices)
+            && Arc::ptr_eq(&self.filtered_indices, &other.filtered_indices)
+            && self.filter_fn == other.filter_fn
+            && Arc::ptr_eq(&self.filter_input, &other.filter_input)
+            && self.search_key == other.search_key
+            && Arc::ptr_eq(&self.predicate, &other.predicate)
+            && Arc::ptr_eq(&self.filter_revision, &other.filter_revision)
+            && Arc::ptr_eq(&self.meta, &other.meta)
+            && Arc::ptr_eq(&self.generation, &other.generation)
+            && Arc::ptr_eq(&self.pristine, &other.pristine)
+            && Rc::ptr_eq(&self.subscribers, &other.subscribers)
+    }
+}
+
+impl<T> SelectState<T> {
+    // TODO: make filter optional?
+    pub fn new<I: Into<Arc<[T]>>, F: Into<SelectFilter<T>>>(
+        options: I,
+        selection: Selection,
+        filter_fn: F,
+    ) -> Self {
+        Self::with_options(Options::Shared(options.into()), selection, filter_fn.into())
+    }
+
+    /// Create a state over a static option set (e.g. a constant table), which
+    /// doesn't need to be allocated or copied per instance
+    pub fn from_static<F: Into<SelectFilter<T>>>(
+        options: &'static [T],
+        selection: Selection,
+        filter_fn: F,
+    ) -> Self {
+        Self::with_options(Options::Static(options), selection, filter_fn.into())
+    }
+
+    fn with_options(options: Options<T>, selection: Selection, filter_fn: SelectFilter<T>) -> Self {
+        let state = Self {
+            options,
+            layout: None,
+            selected_indices: Arc::new(RwLock::new(selection)),
+            filtered_indices: Arc::new(RwLock::new(Filtered::All)),
+
+            filter_fn,
+            filter_input: Arc::new(RwLock::new(None)),
+
+            search_key: None,
+            search_keys: None,
+            predicate: Arc::new(RwLock::new(None)),
+            filter_revision: Arc::new(AtomicUsize::new(0)),
+            meta: Arc::new(RwLock::new(HashMap::new())),
+            meta_revision: Arc::new(AtomicUsize::new(0)),
+
+            generation: Arc::new(AtomicUsize::new(0)),
+            pristine: Arc::new(AtomicBool::new(true)),
+
+            subscribers: Rc::new(RefCell::new(Vec::new())),
+            next_subscriber: Rc::new(Cell::new(0)),
+        };
+        // A context filter restricts the options from the start
+        if state.is_restricted() {
+            state.unfilter_now();
+        }
+        state
+    }
+
+    /// Counter that changes whenever the filtered items or selection change
+    #[doc(hidden)]
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    fn touch(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Call `listener` whenever the selection, filtered items or options change
+    /// through any clone of this state. Returns an id for `unsubscribe`.
+    ///
+    /// Listeners are called after the state's locks are released, so they may
+    /// read (or mutate) the state.
+    pub fn subscribe<F: Fn() + 'static>(&self, listener: F) -> usize {
+        let id = self.next_subscriber.get();
+        self.next_subscriber.set(id + 1);
+        self.subscribers
+            .borrow_mut()
+            .push((id, Rc::new(listener) as Subscriber));
+        id
+    }
+
+    pub fn unsubscribe(&self, id: usize) {
+        self.subscribers.borrow_mut().retain(|(i, _)| *i != id);
+    }
+
+    /// Notify subscribers if the state changed since generation `before`
+    fn notify_since(&self, before: usize) {
+        if self.generation() == before {
+            return;
+        }
+
+        // Clone the listeners out, so they can (un)subscribe while being called
+        let listeners = self
+            .subscribers
+            .borrow()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect::<Vec<_>>();
+        for listener in listeners {
+            listener();
+        }
+    }
+
+    /// Run a mutation, then notify subscribers once its locks are released
+    fn notifying<R, F: FnOnce() -> R>(&self, mutate: F) -> R {
+        let before = self.generation();
+        let result = mutate();
+        self.notify_since(before);
+        result
+    }
+
+    /// True until a selection has been explicitly made through this state (the
+    /// initial `Selection` doesn't count). Used to show a prompt for
+    /// `Selection::AlwaysOne` instead of the implicit default.
+    pub fn is_pristine(&self) -> bool {
+        self.pristine.load(Ordering::Relaxed)
+    }
+
+    /// Mark the selection as explicitly made
+    fn mark_chosen(&self) {
+        if self.pristine.swap(false, Ordering::Relaxed) {
+            self.touch();
+        }
+    }
+
+    /// Whether the user has actually chosen a value: false if nothing is selected,
+    /// or if a `Selection::AlwaysOne` still holds its implicit initial value.
+    ///
+    /// Use this to validate a form before submission when the select shows a prompt.
+    pub fn has_choice(&self) -> bool {
+        if let Ok(inner) = self.selected_indices.read() {
+            match *inner {
+                Selection::AlwaysOne(_) => !self.is_pristine(),
+                ref selection => !selection.is_empty(),
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Search options by a precomputed key: `key_filter` is called with each
+    /// option's key and the query, in place of the state's filter with the option.
+    ///
+    /// Keys are extracted and lowercased once per option (and again when options
+    /// are replaced), and the query is lowercased once per search, so neither has
+    /// to be normalized on every call (e.g. `SelectKeyFilter::contains()`, or
+    /// `|key: &str, query: &str| key.starts_with(query)`).
+    pub fn with_search_key<K: Into<SelectSearchKey<T>>, F: Into<SelectKeyFilter>>(
+        mut self,
+        search_key: K,
+        key_filter: F,
+    ) -> Self {
+        let search_key = search_key.into();
+        self.search_keys = Some(Self::compute_search_keys(&search_key, &self.options));
+        self.search_key = Some((search_key, key_filter.into()));
+        self.apply_query();
+        self
+    }
+
+    /// Start with the options filtered by `query` (e.g. restored from the URL).
+    /// `Select` components use it as their initial search text.
+    pub fn with_query<S: Into<String>>(self, query: S) -> Self {
+        let query = query.into();
+        self.set_query(if query.is_empty() { None } else { Some(query) });
+        self.apply_query();
+        self
+    }
+
+    /// Filter by the stored query (showing every option without one)
+    fn apply_query(&self) {
+        match self.query() {
+            Some(query) => self.filter_now(&query),
+            None => self.unfilter_now(),
+        }
+    }
+
+    fn compute_search_keys(search_key: &SelectSearchKey<T>, options: &[T]) -> Arc<[String]> {
+        options
+            .iter()
+            .map(|item| search_key.call(item).to_lowercase())
+            .collect()
+    }
+
+    fn set_options(&mut self, options: Options<T>) {
+        if let Some((ref search_key, _)) = self.search_key {
+            self.search_keys = Some(Self::compute_search_keys(search_key, &options));
+        }
+        self.options = options;
+        // Entries refer to the old option indices
+        self.layout = None;
+        self.touch();
+    }
+
+    pub fn is_multiple(&self) -> bool {
+        if let Ok(inner) = self.selected_indices.read() {
+            inner.is_multiple()
+        } else {
+            // TODO: handle lock error?
+            false
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        if let Ok(inner) = self.selected_indices.read() {
+            inner.is_nullable()
+        } else {
+            // TODO: handle lock error?
+            false
+        }
+    }
+
+    /// Replace the option set. You should probably use `replace_options_reselecting`
+    ///
+    /// Only this handle sees the new options, so pass it to the `Select` again.
+    /// An open dropdown keeps its focus and query, which is re-run against the
+    /// new options.
+    pub async fn replace_options<I: Into<Arc<[T]>>>(&mut self, options: I) {
+        let before = self.generation();
+        if let Ok(mut inner) = self.selected_indices.write() {
+            match *inner {
+                Selection::MaybeOne(_) => *inner = Selection::none(),
+                Selection::AlwaysOne(_) => {
+                    *inner = Selection::one(0);
+                    self.pristine.store(true, Ordering::Relaxed);
+                }
+                Selection::Multiple(_) => *inner = Selection::empty(),
+            }
+        }
+        self.remap_meta(|_| None);
+        self.set_options(Options::Shared(options.into()));
+        self.refilter().await;
+        self.notify_since(before);
+    }
+
+    /// Replace the existing options and attempt to reeselect the existing selections
+    /// (if `Selection::AlwaysOne`, it will default to index 0 if not found).
+    ///
+    /// Returns which selections survived (e.g. to tell the user that some of
+    /// their choices are no longer available). Only the selected options (and
+    /// those with metadata) are looked up in the new options, so the report has
+    /// no `mapping`; use `replace_options_reselecting_by_key` to get one.
+    pub async fn replace_options_reselecting<I: Into<Arc<[T]>>, F: Fn(&T, &T) -> bool>(
+        &mut self,
+        options: I,
+        selection_eq: F,
+    ) -> ReselectionReport {
+        let new_options: Arc<[T]> = options.into();
+        let mut tracked = match self.selected_indices.read() {
+            Ok(inner) => inner.as_set(),
+            Err(_) => BTreeSet::new(),
+        };
+        if let Ok(meta) = self.meta.read() {
+            tracked.extend(meta.keys().copied());
+        }
+        let positions = tracked
+            .into_iter()
+            .map(|index| {
+                let position = self
+                    .options
+                    .get(index)
+                    .and_then(|item| new_options.iter().position(|t| (selection_eq)(item, t)));
+                (index, position)
+            })
+            .collect::<HashMap<usize, Option<usize>>>();
+        self.replace_options_mapped(new_options, Vec::new(), move |index| {
+            positions.get(&index).copied().flatten()
+        })
+        .await
+    }
+
+    /// Like `replace_options_reselecting`, treating options with the same
+    /// label as the same item (e.g. `SelectDisplay::from_display()`).
+    ///
+    /// Labels of the new options are computed once and looked up, so this is
+    /// cheaper than comparing labels in a `selection_eq` closure.
+    pub async fn replace_options_reselecting_by_display<I: Into<Arc<[T]>>>(
+        &mut self,
+        options: I,
+        display: &SelectDisplay<T>,
+    ) -> ReselectionReport {
+        self.replace_options_reselecting_by_key(options, |item| display.call(item))
+            .await
+    }
+
+    /// Like `replace_options_reselecting`, treating options with the same key
+    /// (e.g. a database ID) as the same item.
+    ///
+    /// Keys are hashed, so this takes linear time rather than scanning the new
+    /// options for every old one (which matters for large option sets).
+    pub async fn replace_options_reselecting_by_key<
+        I: Into<Arc<[T]>>,
+        K: Hash + Eq,
+        F: Fn(&T) -> K,
+    >(
+        &mut self,
+        options: I,
+        key_fn: F,
+    ) -> ReselectionReport {
+        let new_options: Arc<[T]> = options.into();
+        let mut positions = HashMap::with_capacity(new_options.len());
+        for (i, item) in new_options.iter().enumerate() {
+            positions.entry(key_fn(item)).or_insert(i);
+        }
+        let mapping = self
+            .options
+            .iter()
+            .map(|item| positions.get(&key_fn(item)).copied())
+            .collect::<Vec<_>>();
+        let lookup = mapping.clone();
+        self.replace_options_mapped(new_options, mapping, move |index| {
+            lookup.get(index).copied().flatten()
+        })
+        .await
+    }
+
+    /// Replace the options, moving each selection (and metadata) to its
+    /// `new_index`. `mapping` is passed on to the report.
+    async fn replace_options_mapped<F: Fn(usize) -> Option<usize>>(
+        &mut self,
+        new_options: Arc<[T]>,
+        mapping: Vec<Option<usize>>,
+        new_index: F,
+    ) -> ReselectionReport {
+        let before = self.generation();
+        let mut report = ReselectionReport {
+            mapping,
+            ..ReselectionReport::default()
+        };
+        if let Ok(mut inner) = self.selected_indices.write() {
+            for index in inner.as_set() {
+                match new_index(index) {
+                    Some(new_index) => report.kept.push(new_index),
+                    None => report.dropped.push(index),
+                }
+            }
+
+            *inner = match *inner {
+                Selection::MaybeOne(_) => Selection::MaybeOne(report.kept.first().copied()),
+                Selection::AlwaysOne(_) => {
+                    if report.kept.is_empty() {
+                        self.pristine.store(true, Ordering::Relaxed);
+                    }
+                    Selection::one(report.kept.first().copied().unwrap_or_default())
+                }
+                Selection::Multiple(_) => Selection::multiple(report.kept.iter().copied()),
+            };
+        }
+        debug!(
+            "reselected {} of {} selections",
+            report.kept.len(),
+            report.kept.len() + report.dropped.len()
+        );
+        self.remap_meta(&new_index);
+        self.set_options(Options::Shared(new_options));
+        self.refilter().await;
+        self.notify_since(before);
+        report
+    }
+
+    /// Like `replace_options_reselecting`, emitting the report to `onreport` once done
+    #[cfg(feature = "yew")]
+    pub async fn replace_options_reselecting_notify<I: Into<Arc<[T]>>, F: Fn(&T, &T) -> bool>(
+        &mut self,
+        options: I,
+        selection_eq: F,
+        onreport: Callback<ReselectionReport>,
+    ) {
+        let report = self
+            .replace_options_reselecting(options, selection_eq)
+            .await;
+        onreport.emit(report);
+    }
+
+    /// Global indices of the options matching `input` (by search key if set,
+    /// otherwise by filter)
+    #[doc(hidden)]
+    pub fn matching_indices(&self, input: &str) -> Vec<usize> {
+        let matching =
+            if let (Some((_, key_filter)), Some(keys)) = (&self.search_key, &self.search_keys) {
+                let needle = input.to_lowercase();
+                keys.iter()
+                    .enumerate()
+                    .filter_map(|(i, key)| {
+                        if key_filter.call(key, &needle) {
+                            Some(i)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<usize>>()
+            } else {
+                self.options
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, item)| {
+                        if self.filter_fn.call(item, input) {
+                            Some(i)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<usize>>()
+            };
+        self.allowed(matching)
+    }
+
+    /// The given indices, without the options rejected by the predicate (see
+    /// `set_predicate`), or by a `SelectFilterWithCtx`'s context
+    #[doc(hidden)]
+    pub fn allowed<I: IntoIterator<Item = usize>>(&self, indices: I) -> Vec<usize> {
+        // TODO: handle poison
+        let predicate = self.predicate.read().ok();
+        let predicate = predicate.as_deref().and_then(Option::as_ref);
+        if predicate.is_none() && !self.filter_fn.applies_without_query() {
+            return indices.into_iter().collect();
+        }
+        indices
+            .into_iter()
+            .filter(|&i| self.is_allowed(predicate, i))
+            .collect()
+    }
+
+    /// Whether the option at `index` passes `predicate`, and the filter with an
+    /// empty query if it applies without one
+    fn is_allowed(&self, predicate: Option<&SelectPredicate<T>>, index: usize) -> bool {
+        self.options.get(index).map_or(false, |item| {
+            predicate.map_or(true, |predicate| predicate.call(item))
+                && (!self.filter_fn.applies_without_query() || self.filter_fn.call(item, ""))
+        })
+    }
+
+    /// Whether `allowed` can reject options
+    fn is_restricted(&self) -> bool {
+        self.filter_fn.applies_without_query()
+            || self.predicate.read().map_or(false, |p| p.is_some())
+    }
+
+    /// Re-run the active search (in this state and any `Select` using it), e.g.
+    /// after the context of a `SelectFilterWithCtx` changed
+    pub fn refresh_filter(&self) {
+        let before = self.generation();
+        self.filter_revision.fetch_add(1, Ordering::Relaxed);
+        match self.query() {
+            Some(query) => self.filter_now(&query),
+            None => self.unfilter_now(),
+        }
+        self.notify_since(before);
+    }
+
+    /// Counter bumped by `refresh_filter`
+    #[doc(hidden)]
+    pub fn filter_revision(&self) -> usize {
+        self.filter_revision.load(Ordering::Relaxed)
+    }
+
+    /// Restrict the options to those `predicate` accepts (e.g. from a "show
+    /// archived" toggle), on top of any search, without touching the query.
+    /// `None` lifts the restriction.
+    pub fn set_predicate(&self, predicate: Option<SelectPredicate<T>>) {
+        let before = self.generation();
+        if let Ok(mut inner) = self.predicate.write() {
+            *inner = predicate;
+        } else {
+            // TODO: handle poison
+        }
+        match self.query() {
+            Some(query) => self.filter_now(&query),
+            None => self.unfilter_now(),
+        }
+        self.notify_since(before);
+    }
+
+    /// Sort option indices by comparing their options with `order` (stable, so
+    /// equal options keep their relative order)
+    #[doc(hidden)]
+    pub fn sort_indices(&self, indices: &mut [usize], order: &SelectOrder<T>) {
+        indices.sort_by(|&a, &b| match (self.options.get(a), self.options.get(b)) {
+            (Some(item_a), Some(item_b)) => order.call(item_a, item_b),
+            _ => a.cmp(&b),
+        });
+    }
+
+    /// Global index of the first option matching `input` (by search key if set,
+    /// otherwise by filter) and accepted by the predicate, without changing the
+    /// filtered set
+    pub fn first_match(&self, input: &str) -> Option<usize> {
+        let predicate = self.predicate.read().ok();
+        let predicate = predicate.as_deref().and_then(Option::as_ref);
+        let allowed = |i: usize| self.is_allowed(predicate, i);
+        if let (Some((_, key_filter)), Some(keys)) = (&self.search_key, &self.search_keys) {
+            let needle = input.to_lowercase();
+            (0..keys.len()).find(|&i| key_filter.call(&keys[i], &needle) && allowed(i))
+        } else {
+            (0..self.options.len()).find(|&i| {
+                self.options
+                    .get(i)
+                    .map_or(false, |item| self.filter_fn.call(item, input))
+                    && allowed(i)
+            })
+        }
+    }
+
+    async fn filter_inner(&self, input: &str) {
+        self.filter_now(input);
+    }
+
+    fn filter_now(&self, input: &str) {
+        let start = now();
+        if let Ok(mut filtered_indices) = self.filtered_indices.write() {
+            let indices = self.matching_indices(input);
+
+            debug!(
+                "filtered {:?}: {} of {} options in {:.2}ms",
+                input,
+                indices.len(),
+                self.options.len(),
+                now() - start
+            );
+
+            *filtered_indices = if indices.is_empty() {
+                Filtered::None
+            } else {
+                Filtered::Some(indices)
+            };
+            self.touch();
+        }
+    }
+
+    async fn refilter(&self) {
+        if let Ok(input) = self.filter_input.read() {
+            if let Some(ref input) = *input {
+                self.filter_inner(input).await;
+            } else {
+                self.unfilter_inner().await;
+            }
+        }
+        // TODO: handle errors
+    }
+
+    pub async fn filter(&self, input: &str) {
+        let before = self.generation();
+        if input.is_empty() {
+            self.set_query(None);
+            self.unfilter_inner().await
+        } else {
+            self.set_query(Some(input.to_string()));
+            self.filter_inner(input).await;
+        }
+        self.notify_since(before);
+    }
+
+    pub async fn unfilter(&self) {
+        let before = self.generation();
+        self.set_query(None);
+        self.unfilter_inner().await;
+        self.notify_since(before);
+    }
+
+    /// The search text of the active `filter`, or typed into a `Select` using
+    /// this state (the last one typed into, if several share it). `None` if
+    /// nothing is searched.
+    pub fn query(&self) -> Option<String> {
+        if let Ok(input) = self.filter_input.read() {
+            input.clone()
+        } else {
+            // TODO: handle poison
+            None
+        }
+    }
+
+    /// Record the search text typed into a `Select`, which filters by itself
+    #[doc(hidden)]
+    pub fn set_search_text(&self, text: &str) {
+        self.set_query(if text.is_empty() {
+            None
+        } else {
+            Some(text.to_string())
+        });
+    }
+
+    fn set_query(&self, query: Option<String>) {
+        if let Ok(mut filter_input) = self.filter_input.write() {
+            *filter_input = query;
+        } else {
+            // TODO: handle poison
+        }
+    }
+
+    async fn unfilter_inner(&self) {
+        self.unfilter_now();
+    }
+
+    fn unfilter_now(&self) {
+        let unfiltered = if self.is_restricted() {
+            match self.allowed(0..self.options.len()) {
+                indices if indices.is_empty() => Filtered::None,
+                indices => Filtered::Some(indices),
+            }
+        } else {
+            Filtered::All
+        };
+        if let Ok(mut inner) = self.filtered_indices.write() {
+            *inner = unfiltered;
+            self.touch();
+        }
+    }
+
+    // Expose the internal api of the options
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.options.get(index)
+    }
+    pub fn iter(&self) -> std::slice::Iter<T> {
+        self.options.iter()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        if let Ok(selected) = self.selected_indices.read() {
+            selected.includes(&index)
+        } else {
+            false
+        }
+    }
+
+    pub fn first_selected(&self) -> Option<(usize, &T)> {
+        if let Ok(selected) = self.selected_indices.read() {
+            match *selected {
+                Selection::MaybeOne(None) => {}
+                Selection::AlwaysOne(index) | Selection::MaybeOne(Some(index)) => {
+                    if let Some(item) = self.options.get(index) {
+                        return Some((index, item));
+                    }
+                }
+                Selection::Multiple(ref set) => {
+                    if let Some(&index) = set.iter().next() {
+                        if let Some(item) = self.options.get(index) {
+                            return Some((index, item));
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn selected_items(&self) -> Vec<(usize, &T)> {
+        if let Ok(selected) = self.selected_indices.read() {
+            match *selected {
+                Selection::MaybeOne(None) => Vec::new(),
+                Selection::AlwaysOne(index) | Selection::MaybeOne(Some(index)) => {
+                    if let Some(item) = self.options.get(index) {
+                        vec![(index, item)]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                Selection::Multiple(ref set) => {
+                    // let mut indices = set.iter().cloned().collect::<Vec<_>>();
+                    // indices.sort_unstable();
+
+                    let mut selected_items = Vec::with_capacity(set.len());
+                    for &index in set {
+                        if let Some(item) = self.options.get(index) {
+                            selected_items.push((index, item))
+                        }
+                    }
+                    selected_items
+                }
+            }
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Selected indices which don't point at an option (e.g. left over after the
+    /// options were replaced through another handle to this state)
+    pub fn invalid_selections(&self) -> Vec<usize> {
+        if let Ok(selected) = self.selected_indices.read() {
+            selected
+                .as_set()
+                .into_iter()
+                .filter(|&index| index >= self.options.len())
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Annotate the option at `index` (e.g. disable or pin it), replacing its
+    /// previous metadata. An empty `OptionMeta` removes it.
+    ///
+    /// Metadata follows its option through `replace_options_reselecting`, and
+    /// is dropped by `replace_options`.
+    pub fn set_meta(&self, index: usize, meta: OptionMeta) {
+        self.notifying(|| {
+            if index >= self.options.len() {
+                return;
+            }
+
+            if let Ok(mut inner) = self.meta.write() {
+                let changed = if meta.is_empty() {
+                    inner.remove(&index).is_some()
+                } else {
+                    inner.insert(index, meta.clone()).as_ref() != Some(&meta)
+                };
+                if changed {
+                    debug!("set meta of {} to {:?}", index, meta);
+                    self.meta_revision.fetch_add(1, Ordering::Relaxed);
+                    self.touch();
+                }
+            } else {
+                // TODO: handle poison
+            }
+        })
+    }
+
+    /// Metadata of the option at `index`, if any was set
+    pub fn meta(&self, index: usize) -> Option<OptionMeta> {
+        self.meta
+            .read()
+            .ok()
+            .and_then(|inner| inner.get(&index).cloned())
+    }
+
+    /// Remove the metadata of every option
+    pub fn clear_meta(&self) {
+        self.notifying(|| self.remap_meta(|_| None))
+    }
+
+    pub fn is_disabled(&self, index: usize) -> bool {
+        self.meta.read().map_or(false, |inner| {
+            inner.get(&index).map_or(false, |meta| meta.disabled)
+        })
+    }
+
+    /// Indices of the pinned options, in ascending order
+    pub fn pinned(&self) -> Vec<usize> {
+        let mut pinned = self.meta.read().map_or_else(
+            |_| Vec::new(),
+            |inner| {
+                inner
+                    .iter()
+                    .filter(|(_, meta)| meta.pinned)
+                    .map(|(&index, _)| index)
+                    .collect()
+            },
+        );
+        pinned.sort_unstable();
+        pinned
+    }
+
+    /// Move the pinned options among `items` (`(index, selected)` pairs) to the
+    /// front, keeping the order otherwise
+    #[doc(hidden)]
+    pub fn pinned_first(&self, items: &mut [(usize, bool)]) {
+        let pinned = self.pinned();
+        if !pinned.is_empty() {
+            items.sort_by_key(|(index, _)| pinned.binary_search(index).is_err());
+        }
+    }
+
+    /// Counter that changes whenever any option's metadata changes
+    #[doc(hidden)]
+    pub fn meta_revision(&self) -> usize {
+        self.meta_revision.load(Ordering::Relaxed)
+    }
+
+    /// Move the metadata to the new index of each option (dropping it if `None`)
+    fn remap_meta<F: Fn(usize) -> Option<usize>>(&self, new_index: F) {
+        if let Ok(mut inner) = self.meta.write() {
+            if inner.is_empty() {
+                return;
+            }
+            *inner = inner
+                .drain()
+                .filter_map(|(index, meta)| new_index(index).map(|index| (index, meta)))
+                .collect();
+            self.meta_revision.fetch_add(1, Ordering::Relaxed);
+            self.touch();
+        }
+    }
+
+    /// Dump of the state (sizes, selection, query and counters), e.g. to
+    /// attach to a bug report with `SelectDebug::to_json`
+    pub fn debug_snapshot(&self) -> SelectDebug {
+        let filtered = match self.filtered_indices.read() {
+            Ok(filtered) => match *filtered {
+                Filtered::All => self.options.len(),
+                Filtered::Some(ref indices) => indices.len(),
+                Filtered::None => 0,
+            },
+            Err(_) => 0,
+        };
+        let (mode, selected) = match self.selected_indices.read() {
+            Ok(selection) => {
+                let mode = match *selection {
+                    Selection::AlwaysOne(_) => "AlwaysOne",
+                    Selection::MaybeOne(_) => "MaybeOne",
+                    Selection::Multiple(_) => "Multiple",
+                };
+                (mode, selection.as_set().into_iter().collect())
+            }
+            Err(_) => ("Poisoned", Vec::new()),
+        };
+        SelectDebug {
+            options: self.options.len(),
+            filtered,
+            mode,
+            selected,
+            invalid: self.invalid_selections(),
+            query: self.query(),
+            pristine: self.is_pristine(),
+            generation: self.generation(),
+            filter_revision: self.filter_revision(),
+            layout: self.layout.as_ref().map(|layout| layout.len()),
+            meta: self.meta.read().map_or(0, |inner| inner.len()),
+            has_search_key: self.search_key.is_some(),
+            has_predicate: self
+                .predicate
+                .read()
+                .map_or(false, |predicate| predicate.is_some()),
+        }
+    }
+
+    pub fn first_filtered(&self) -> Option<(usize, &T)> {
+        if let Ok(filtered) = self.filtered_indices.read() {
+            match *filtered {
+                Filtered::All => {
+                    if let Some(item) = self.options.first() {
+                        return Some((0, item));
+                    }
+                }
+                Filtered::Some(ref indices) => {
+                    if let Some(&index) = indices.first() {
+                        if let Some(item) = self.options.get(index) {
+                            return Some((index, item));
+                        }
+                    }
+                }
+                Filtered::None => {}
+            }
+        }
+
+        None
+    }
+
+    // Get an option item an it's global index using it's relative position in the filter list
+    pub fn get_filtered(&self, position: usize) -> Option<(usize, &T)> {
+        if let Ok(filtered) = self.filtered_indices.read() {
+            match *filtered {
+                Filtered::All => {
+                    // If no filtering, position is equivalent to index
+                    if let Some(item) = self.options.get(position) {
+                        return Some((position, item));
+                    }
+                }
+                Filtered::Some(ref indices) => {
+                    // If filtered, we need to find the global index of the item at this position
+                    if let Some(&index) = indices.get(position) {
+                        if let Some(item) = self.options.get(index) {
+                            return Some((index, item));
+                        }
+                    }
+                }
+                Filtered::None => {} // No elements means nothing at this position
+            }
+        }
+
+        None
+    }
+
+    pub fn filtered_items(&self) -> Vec<(usize, bool, &T)> {
+        if let (Ok(filtered), Ok(selected)) =
+            (self.filtered_indices.read(), self.selected_indices.read())
+        {
+            match *filtered {
+                Filtered::All => self
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| (i, selected.includes(&i), item))
+                    .collect::<Vec<_>>(),
+                Filtered::Some(ref indices) => {
+                    let mut filtered_items = Vec::with_capacity(indices.len());
+                    for &index in indices {
+                        if let Some(item) = self.options.get(index) {
+                            filtered_items.push((index, selected.includes(&index), item))
+                        }
+                    }
+                    filtered_items
+                }
+
+                Filtered::None => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// `(index, selected)` for each of `indices`, taking the selection lock once
+    #[doc(hidden)]
+    pub fn with_selected<I: IntoIterator<Item = usize>>(&self, indices: I) -> Vec<(usize, bool)> {
+        if let Ok(selected) = self.selected_indices.read() {
+            indices
+                .into_iter()
+                .map(|i| (i, selected.includes(&i)))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Select an index from the options.
+    /// Returns true if the selection has changed.
+    pub fn select(&self, index: usize) -> bool {
+        self.notifying(|| {
+            if index >= self.options.len() {
+                return false;
+            }
+
+            if let Ok(mut inner) = self.selected_indices.write() {
+                let changed = inner.select(index);
+                if changed {
+                    debug!("selected {}", index);
+                    self.touch();
+                }
+                self.mark_chosen();
+                changed
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Deselect an index from the options (which may be a dangling index, see
+    /// `invalid_selections`).
+    /// Returns true if the selection has changed.
+    pub fn deselect(&self, index: usize) -> bool {
+        self.notifying(|| {
+            if let Ok(mut inner) = self.selected_indices.write() {
+                let changed = inner.deselect(index);
+                if changed {
+                    debug!("deselected {}", index);
+                    self.touch();
+                }
+                changed
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Clear the selected items.
+    /// Returns true if the selection has changed.
+    pub fn clear(&self) -> bool {
+        self.notifying(|| {
+            if let Ok(mut inner) = self.selected_indices.write() {
+                let changed = inner.clear();
+                if changed {
+                    debug!("cleared selection");
+                    self.touch();
+                }
+                changed
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Select several indices from the options, taking the lock once.
+    /// Returns true if the selection has changed.
+    ///
+    /// In single selection modes, the last valid index wins.
+    pub fn select_many(&self, indices: &[usize]) -> bool {
+        self.notifying(|| {
+            if let Ok(mut inner) = self.selected_indices.write() {
+                let mut changed = false;
+                for &index in indices.iter().filter(|&&i| i < self.options.len()) {
+                    changed |= inner.select(index);
+                }
+                if changed {
+                    debug!("selected {:?}", indices);
+                    self.touch();
+                }
+                self.mark_chosen();
+                changed
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Deselect several indices from the options, taking the lock once.
+    /// Returns true if the selection has changed.
+    pub fn deselect_many(&self, indices: &[usize]) -> bool {
+        self.notifying(|| {
+            if let Ok(mut inner) = self.selected_indices.write() {
+                let mut changed = false;
+                for &index in indices {
+                    changed |= inner.deselect(index);
+                }
+                if changed {
+                    debug!("deselected {:?}", indices);
+                    self.touch();
+                }
+                changed
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Replace the whole selection. Indices outside of the options are dropped
+    /// (or reset to 0 for `Selection::AlwaysOne`).
+    /// Returns true if the selection has changed.
+    pub fn set_selection(&self, selection: Selection) -> bool {
+        self.notifying(|| {
+            let len = self.options.len();
+            let selection = match selection {
+                Selection::AlwaysOne(index) if index >= len => Selection::one(0),
+                Selection::MaybeOne(Some(index)) if index >= len => Selection::none(),
+                Selection::Multiple(set) => {
+                    Selection::multiple(set.into_iter().filter(|&i| i < len))
+                }
+                selection => selection,
+            };
+
+            if let Ok(mut inner) = self.selected_indices.write() {
+                if *inner == selection {
+                    false
+                } else {
+                    debug!("set selection {:?}", selection);
+                    *inner = selection;
+                    self.touch();
+                    self.mark_chosen();
+                    true
+                }
+            } else {
+                false
+            }
+        })
+    }
+}
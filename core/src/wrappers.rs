@@ -0,0 +1,556 @@
+use std::{
+    cmp::Ordering,
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
+
+// Use the Box to make sure we're not doing a Arc::ptr_eq on dyn objects (since rust doesn't like that)
+type SelectFilterContainer<T> = Box<dyn Fn(&T, &str) -> bool>;
+
+pub struct SelectFilter<T> {
+    inner: Arc<SelectFilterContainer<T>>,
+    /// Also restricts the options while nothing is searched, by calling it with
+    /// an empty query (for a `SelectFilterWithCtx`, so its context always applies)
+    without_query: bool,
+}
+
+impl<T> PartialEq for SelectFilter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectFilter<T> {
+    pub fn new<F: Fn(&T, &str) -> bool + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectFilterContainer<T>),
+            without_query: false,
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T, input: &str) -> bool {
+        (self.inner)(item, input)
+    }
+
+    /// Whether the filter restricts the options without a query too
+    pub(crate) fn applies_without_query(&self) -> bool {
+        self.without_query
+    }
+}
+
+impl<T, F: Fn(&T, &str) -> bool + 'static> From<F> for SelectFilter<T> {
+    fn from(f: F) -> Self {
+        SelectFilter::new(f)
+    }
+}
+
+impl<T> Clone for SelectFilter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            without_query: self.without_query,
+        }
+    }
+}
+
+type SelectDisplayContainer<T> = Box<dyn Fn(&T) -> String>;
+
+pub struct SelectDisplay<T> {
+    inner: Arc<SelectDisplayContainer<T>>,
+}
+
+impl<T> PartialEq for SelectDisplay<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectDisplay<T> {
+    pub fn new<F: Fn(&T) -> String + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectDisplayContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> String {
+        (self.inner)(item)
+    }
+}
+
+impl<T, F: Fn(&T) -> String + 'static> From<F> for SelectDisplay<T> {
+    fn from(f: F) -> Self {
+        SelectDisplay::new(f)
+    }
+}
+
+impl<T> Clone for SelectDisplay<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectSearchKeyContainer<T> = Box<dyn Fn(&T) -> String>;
+
+/// Extracts the text an option is searched by. Keys are computed (and lowercased)
+/// once per option, rather than on every keystroke, and passed to a
+/// [`SelectKeyFilter`] in place of the options (see `SelectState::with_search_key`).
+pub struct SelectSearchKey<T> {
+    inner: Arc<SelectSearchKeyContainer<T>>,
+}
+
+impl<T> PartialEq for SelectSearchKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectSearchKey<T> {
+    pub fn new<F: Fn(&T) -> String + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectSearchKeyContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> String {
+        (self.inner)(item)
+    }
+}
+
+impl<T, F: Fn(&T) -> String + 'static> From<F> for SelectSearchKey<T> {
+    fn from(f: F) -> Self {
+        SelectSearchKey::new(f)
+    }
+}
+
+impl<T> Clone for SelectSearchKey<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectKeyFilterContainer = Box<dyn Fn(&str, &str) -> bool>;
+
+/// Filter over precomputed search keys: called with an option's (lowercased)
+/// key and the lowercased query (see `SelectState::with_search_key`)
+pub struct SelectKeyFilter {
+    inner: Rc<SelectKeyFilterContainer>,
+}
+
+impl PartialEq for SelectKeyFilter {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl SelectKeyFilter {
+    pub fn new<F: Fn(&str, &str) -> bool + 'static>(f: F) -> Self {
+        Self {
+            inner: Rc::new(Box::new(f) as SelectKeyFilterContainer),
+        }
+    }
+
+    /// Keys containing the query
+    pub fn contains() -> Self {
+        Self::new(|key: &str, query: &str| key.contains(query))
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, key: &str, query: &str) -> bool {
+        (self.inner)(key, query)
+    }
+}
+
+impl<F: Fn(&str, &str) -> bool + 'static> From<F> for SelectKeyFilter {
+    fn from(f: F) -> Self {
+        SelectKeyFilter::new(f)
+    }
+}
+
+impl Clone for SelectKeyFilter {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectValueContainer<T, V> = Box<dyn Fn(&T) -> V>;
+
+/// Extracts the value (e.g. a database ID) that a `TypedSelect` emits for an option
+pub struct SelectValue<T, V> {
+    inner: Arc<SelectValueContainer<T, V>>,
+}
+
+impl<T, V> PartialEq for SelectValue<T, V> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T, V> SelectValue<T, V> {
+    pub fn new<F: Fn(&T) -> V + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectValueContainer<T, V>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> V {
+        (self.inner)(item)
+    }
+}
+
+impl<T, V, F: Fn(&T) -> V + 'static> From<F> for SelectValue<T, V> {
+    fn from(f: F) -> Self {
+        SelectValue::new(f)
+    }
+}
+
+impl<T, V> Clone for SelectValue<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectGroupContainer<T> = Box<dyn Fn(&T) -> String>;
+
+/// Assigns each option to a named group, rendered under a header in the dropdown
+pub struct SelectGroup<T> {
+    inner: Arc<SelectGroupContainer<T>>,
+}
+
+impl<T> PartialEq for SelectGroup<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectGroup<T> {
+    pub fn new<F: Fn(&T) -> String + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectGroupContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> String {
+        (self.inner)(item)
+    }
+}
+
+impl<T, F: Fn(&T) -> String + 'static> From<F> for SelectGroup<T> {
+    fn from(f: F) -> Self {
+        SelectGroup::new(f)
+    }
+}
+
+impl<T> Clone for SelectGroup<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectPredicateContainer<T> = Box<dyn Fn(&T) -> bool>;
+
+/// A yes/no test on an option (e.g. whether to draw a divider after it)
+pub struct SelectPredicate<T> {
+    inner: Arc<SelectPredicateContainer<T>>,
+}
+
+impl<T> PartialEq for SelectPredicate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectPredicate<T> {
+    pub fn new<F: Fn(&T) -> bool + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectPredicateContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> bool {
+        (self.inner)(item)
+    }
+}
+
+impl<T, F: Fn(&T) -> bool + 'static> From<F> for SelectPredicate<T> {
+    fn from(f: F) -> Self {
+        SelectPredicate::new(f)
+    }
+}
+
+impl<T> Clone for SelectPredicate<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectActionContainer = Box<dyn Fn()>;
+
+/// Callback run by an action row of the dropdown
+pub struct SelectAction {
+    inner: Rc<SelectActionContainer>,
+}
+
+impl PartialEq for SelectAction {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl SelectAction {
+    pub fn new<F: Fn() + 'static>(f: F) -> Self {
+        Self {
+            inner: Rc::new(Box::new(f) as SelectActionContainer),
+        }
+    }
+
+    /// An action emitting `callback` (e.g. from `link.callback(|_| Msg::Refresh)`)
+    #[cfg(feature = "yew")]
+    pub fn from_callback(callback: yew::Callback<()>) -> Self {
+        Self::new(move || callback.emit(()))
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self) {
+        (self.inner)()
+    }
+}
+
+impl<F: Fn() + 'static> From<F> for SelectAction {
+    fn from(f: F) -> Self {
+        SelectAction::new(f)
+    }
+}
+
+impl Clone for SelectAction {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectImageContainer<T> = Box<dyn Fn(&T) -> Option<String>>;
+
+/// Image URL for an option (e.g. a user's avatar), if it has one
+pub struct SelectImage<T> {
+    inner: Arc<SelectImageContainer<T>>,
+}
+
+impl<T> PartialEq for SelectImage<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectImage<T> {
+    pub fn new<F: Fn(&T) -> Option<String> + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectImageContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> Option<String> {
+        (self.inner)(item)
+    }
+}
+
+impl<T, F: Fn(&T) -> Option<String> + 'static> From<F> for SelectImage<T> {
+    fn from(f: F) -> Self {
+        SelectImage::new(f)
+    }
+}
+
+impl<T> Clone for SelectImage<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: 'static> SelectImage<T> {
+    /// A solid swatch (as an inline SVG) of the CSS color given by `color`
+    pub fn swatch(color: SelectDisplay<T>) -> Self {
+        SelectImage::new(move |item: &T| {
+            Some(format!(
+                "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 1 1'%3E%3Crect width='1' height='1' fill='{}'/%3E%3C/svg%3E",
+                color.call(item).replace('#', "%23")
+            ))
+        })
+    }
+}
+
+type SelectBadgeContainer<T> = Box<dyn Fn(&T) -> Option<String>>;
+
+/// Short text shown in a tag at the end of an option's row (e.g. a count of
+/// open issues next to a project), if the option has one
+pub struct SelectBadge<T> {
+    inner: Arc<SelectBadgeContainer<T>>,
+}
+
+impl<T> PartialEq for SelectBadge<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectBadge<T> {
+    pub fn new<F: Fn(&T) -> Option<String> + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectBadgeContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T) -> Option<String> {
+        (self.inner)(item)
+    }
+}
+
+impl<T, F: Fn(&T) -> Option<String> + 'static> From<F> for SelectBadge<T> {
+    fn from(f: F) -> Self {
+        SelectBadge::new(f)
+    }
+}
+
+impl<T> Clone for SelectBadge<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectOrderContainer<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+/// Compares two options, to order them (e.g. the results of a search)
+pub struct SelectOrder<T> {
+    inner: Arc<SelectOrderContainer<T>>,
+}
+
+impl<T> PartialEq for SelectOrder<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> SelectOrder<T> {
+    pub fn new<F: Fn(&T, &T) -> Ordering + 'static>(f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectOrderContainer<T>),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, a: &T, b: &T) -> Ordering {
+        (self.inner)(a, b)
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering + 'static> From<F> for SelectOrder<T> {
+    fn from(f: F) -> Self {
+        SelectOrder::new(f)
+    }
+}
+
+impl<T> Clone for SelectOrder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+type SelectFilterWithCtxContainer<T, C> = Box<dyn Fn(&T, &str, &C) -> bool>;
+
+/// A filter which also receives a shared context value (e.g. the customer picked
+/// in another select), so the matches can depend on it without rebuilding the
+/// options. Convert it into a [`SelectFilter`] for the state, then call
+/// `set_ctx` followed by `SelectState::refresh_filter` when the context changes.
+///
+/// The context applies while nothing is searched as well: the filter is then
+/// called with an empty query, and only the options it accepts are shown.
+pub struct SelectFilterWithCtx<T, C> {
+    inner: Arc<SelectFilterWithCtxContainer<T, C>>,
+    ctx: Arc<RwLock<C>>,
+}
+
+impl<T, C> PartialEq for SelectFilterWithCtx<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner) && Arc::ptr_eq(&self.ctx, &other.ctx)
+    }
+}
+
+impl<T, C> SelectFilterWithCtx<T, C> {
+    pub fn new<F: Fn(&T, &str, &C) -> bool + 'static>(ctx: C, f: F) -> Self {
+        Self {
+            inner: Arc::new(Box::new(f) as SelectFilterWithCtxContainer<T, C>),
+            ctx: Arc::new(RwLock::new(ctx)),
+        }
+    }
+
+    // TODO: impl Fn when traits stabilize?
+    pub fn call(&self, item: &T, input: &str) -> bool {
+        if let Ok(ctx) = self.ctx.read() {
+            (self.inner)(item, input, &ctx)
+        } else {
+            // TODO: handle poison
+            false
+        }
+    }
+
+    /// Replace the context (for every clone of this filter)
+    pub fn set_ctx(&self, ctx: C) {
+        if let Ok(mut inner) = self.ctx.write() {
+            *inner = ctx;
+        } else {
+            // TODO: handle poison
+        }
+    }
+}
+
+impl<T: 'static, C: 'static> From<SelectFilterWithCtx<T, C>> for SelectFilter<T> {
+    fn from(filter: SelectFilterWithCtx<T, C>) -> Self {
+        SelectFilter {
+            without_query: true,
+            ..SelectFilter::new(move |item: &T, input: &str| filter.call(item, input))
+        }
+    }
+}
+
+impl<T, C> Clone for SelectFilterWithCtx<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            ctx: self.ctx.clone(),
+        }
+    }
+}
+
+/// Why the dropdown has no options to show
+#[derive(Clone, Debug, PartialEq)]
+pub enum EmptyState {
+    /// There are no options at all (or none left to pick)
+    Empty,
+    /// The search matched no options
+    NoMatches { query: String },
+}
@@ -0,0 +1,22 @@
+//! Tests for the `SelectState::debug_snapshot` dump
+
+use futures::executor::block_on;
+
+use search_select_core::{SelectFilter, SelectState, Selection};
+
+#[test]
+fn snapshot_as_json() {
+    let state = SelectState::new(
+        vec!["alpha", "beta", "gamma"],
+        Selection::multiple(vec![0, 2]),
+        SelectFilter::new(|item: &&str, search: &str| item.contains(search)),
+    );
+    block_on(state.filter("a\"\n"));
+
+    assert_eq!(
+        state.debug_snapshot().to_json(),
+        "{\"options\":3,\"filtered\":0,\"mode\":\"Multiple\",\"selected\":[0,2],\"invalid\":[],\
+         \"query\":\"a\\\"\\n\",\"pristine\":true,\"generation\":1,\"filter_revision\":0,\
+         \"layout\":null,\"meta\":0,\"has_search_key\":false,\"has_predicate\":false}"
+    );
+}
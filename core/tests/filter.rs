@@ -0,0 +1,160 @@
+//! Tests for searching a `SelectState`: filters, search keys, predicates and the query
+
+use std::{cell::RefCell, rc::Rc};
+
+use futures::executor::block_on;
+
+use search_select_core::{
+    SelectFilter, SelectFilterWithCtx, SelectKeyFilter, SelectPredicate, SelectState, Selection,
+};
+
+fn state() -> SelectState<&'static str> {
+    SelectState::new(
+        vec!["alpha", "beta", "alphabet", "gamma"],
+        Selection::empty(),
+        SelectFilter::new(|item: &&str, search: &str| item.contains(search)),
+    )
+}
+
+#[test]
+fn first_match_skips_options_rejected_by_predicate() {
+    let state = state();
+    assert_eq!(state.first_match("alpha"), Some(0));
+
+    state.set_predicate(Some(SelectPredicate::new(|item: &&str| *item != "alpha")));
+    assert_eq!(state.first_match("alpha"), Some(2));
+    assert_eq!(
+        state.first_match("alpha"),
+        state.matching_indices("alpha").first().copied()
+    );
+
+    state.set_predicate(Some(SelectPredicate::new(|item: &&str| item.len() < 5)));
+    assert_eq!(state.first_match("alpha"), None);
+}
+
+#[test]
+fn search_key_is_passed_to_key_filter() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let state = state().with_search_key(|item: &&str| item.to_uppercase(), {
+        let seen = seen.clone();
+        move |key: &str, query: &str| {
+            seen.borrow_mut().push((key.to_string(), query.to_string()));
+            key.starts_with(query)
+        }
+    });
+
+    // Keys and query are lowercased, and the key filter decides (by prefix here)
+    assert_eq!(state.matching_indices("ALPHA"), vec![0, 2]);
+    assert_eq!(state.matching_indices("bet"), vec![1]);
+    assert_eq!(
+        seen.borrow()[0],
+        (String::from("alpha"), String::from("alpha"))
+    );
+    assert_eq!(state.first_match("Gam"), Some(3));
+}
+
+#[test]
+fn search_keys_follow_replaced_options() {
+    let mut state =
+        state().with_search_key(|item: &&str| item.to_string(), SelectKeyFilter::contains());
+    block_on(state.replace_options(vec!["delta", "alpha"]));
+    assert_eq!(state.matching_indices("alp"), vec![1]);
+}
+
+fn visible(state: &SelectState<&'static str>) -> Vec<&'static str> {
+    state
+        .filtered_items()
+        .into_iter()
+        .map(|(_, _, item)| *item)
+        .collect()
+}
+
+fn ctx_filter() -> SelectFilterWithCtx<&'static str, char> {
+    SelectFilterWithCtx::new('a', |item: &&str, search: &str, first: &char| {
+        item.starts_with(*first) && item.contains(search)
+    })
+}
+
+#[test]
+fn filter_context_applies_without_query() {
+    let state = SelectState::new(
+        vec!["alpha", "beta", "alphabet", "gamma"],
+        Selection::empty(),
+        ctx_filter(),
+    );
+    assert_eq!(visible(&state), vec!["alpha", "alphabet"]);
+
+    block_on(state.filter("bet"));
+    assert_eq!(visible(&state), vec!["alphabet"]);
+
+    block_on(state.unfilter());
+    assert_eq!(visible(&state), vec!["alpha", "alphabet"]);
+}
+
+#[test]
+fn filter_context_change_applies_on_refresh() {
+    let filter = ctx_filter();
+    let state = SelectState::new(
+        vec!["alpha", "beta", "alphabet", "gamma"],
+        Selection::empty(),
+        filter.clone(),
+    );
+
+    filter.set_ctx('g');
+    state.refresh_filter();
+    assert_eq!(visible(&state), vec!["gamma"]);
+    assert_eq!(state.first_match("a"), Some(3));
+
+    block_on(state.filter("x"));
+    assert!(visible(&state).is_empty());
+
+    filter.set_ctx('b');
+    block_on(state.filter("e"));
+    assert_eq!(visible(&state), vec!["beta"]);
+}
+
+#[test]
+fn empty_filter_clears_the_query() {
+    let state = state();
+    block_on(state.filter("alp"));
+    assert_eq!(state.query(), Some(String::from("alp")));
+    assert_eq!(visible(&state), vec!["alpha", "alphabet"]);
+
+    // An empty search used to be stored as the query and filtered by
+    block_on(state.filter(""));
+    assert_eq!(state.query(), None);
+    assert_eq!(visible(&state).len(), 4);
+}
+
+#[test]
+fn search_text_is_reported_as_query() {
+    let state = state();
+    state.set_search_text("gam");
+    assert_eq!(state.query(), Some(String::from("gam")));
+    state.set_search_text("");
+    assert_eq!(state.query(), None);
+}
+
+#[test]
+fn with_query_starts_filtered() {
+    let state = state().with_query("alp");
+    assert_eq!(state.query(), Some(String::from("alp")));
+    assert_eq!(visible(&state), vec!["alpha", "alphabet"]);
+
+    // An empty query clears it again
+    let state = state.with_query("");
+    assert_eq!(state.query(), None);
+    assert_eq!(visible(&state).len(), 4);
+}
+
+#[test]
+fn with_query_is_reapplied() {
+    let mut state = state()
+        .with_query("BET")
+        .with_search_key(|item: &&str| item.to_string(), SelectKeyFilter::contains());
+    assert_eq!(visible(&state), vec!["beta", "alphabet"]);
+
+    block_on(state.replace_options(vec!["delta", "better"]));
+    assert_eq!(state.query(), Some(String::from("BET")));
+    assert_eq!(visible(&state), vec!["better"]);
+}
@@ -0,0 +1,187 @@
+//! Tests for the pure interaction reducer. Run with `cargo test -p search-select-core`
+
+use search_select_core::interaction::{
+    clamp, reduce, Effect, HighlightSource, Input, Interaction, Key, Menu, RowKind,
+};
+
+const ROWS: [RowKind; 4] = [
+    RowKind::Inert,
+    RowKind::Option(3),
+    RowKind::Inert,
+    RowKind::Option(5),
+];
+
+fn open() -> Interaction {
+    reduce(Interaction::default(), Input::Focus, &Menu::new(&ROWS)).0
+}
+
+#[test]
+fn focus_highlights_first_navigable_row() {
+    let state = open();
+    assert!(state.open);
+    assert_eq!(state.highlight, 1);
+}
+
+#[test]
+fn arrows_skip_inert_rows() {
+    let (state, effects) = reduce(open(), Input::Key(Key::ArrowDown), &Menu::new(&ROWS));
+    assert_eq!(state.highlight, 3);
+    assert!(effects.is_empty());
+
+    let (state, _) = reduce(state, Input::Key(Key::ArrowDown), &Menu::new(&ROWS));
+    assert_eq!(state.highlight, 3);
+
+    let (state, _) = reduce(state, Input::Key(Key::ArrowUp), &Menu::new(&ROWS));
+    assert_eq!(state.highlight, 1);
+}
+
+#[test]
+fn arrow_opens_a_closed_select() {
+    let (state, _) = reduce(
+        Interaction::default(),
+        Input::Key(Key::ArrowDown),
+        &Menu::new(&ROWS),
+    );
+    assert!(state.open);
+    assert_eq!(state.highlight, 1);
+}
+
+#[test]
+fn enter_selects_the_highlighted_option_and_clears_the_search() {
+    let (state, effects) = reduce(open(), Input::Query(String::from("o")), &Menu::new(&ROWS));
+    assert_eq!(effects, vec![Effect::Search(String::from("o"))]);
+
+    let (state, effects) = reduce(state, Input::Key(Key::Enter), &Menu::new(&ROWS));
+    assert!(!state.open);
+    assert!(state.query.is_empty());
+    assert_eq!(
+        effects,
+        vec![Effect::Select(3), Effect::Search(String::new())]
+    );
+}
+
+#[test]
+fn picking_an_inert_row_does_nothing() {
+    let (state, effects) = reduce(open(), Input::Pick(2), &Menu::new(&ROWS));
+    assert!(state.open);
+    assert!(effects.is_empty());
+}
+
+#[test]
+fn other_rows_are_activated() {
+    let rows = [RowKind::Other, RowKind::Option(0)];
+    let state = reduce(Interaction::default(), Input::Focus, &Menu::new(&rows)).0;
+    let (state, effects) = reduce(state, Input::Key(Key::Enter), &Menu::new(&rows));
+    assert!(!state.open);
+    assert_eq!(effects, vec![Effect::Activate(0)]);
+}
+
+#[test]
+fn home_and_end_jump_while_open() {
+    let rows = [
+        RowKind::Inert,
+        RowKind::Option(0),
+        RowKind::Option(1),
+        RowKind::Inert,
+        RowKind::Other,
+    ];
+    let (state, _) = reduce(
+        Interaction::default(),
+        Input::Key(Key::End),
+        &Menu::new(&rows),
+    );
+    assert!(!state.open);
+
+    let state = reduce(state, Input::Focus, &Menu::new(&rows)).0;
+    let (state, _) = reduce(state, Input::Key(Key::End), &Menu::new(&rows));
+    assert_eq!(state.highlight, 4);
+
+    let (state, _) = reduce(state, Input::Key(Key::Home), &Menu::new(&rows));
+    assert_eq!(state.highlight, 1);
+}
+
+fn pointer_menu() -> Menu<'static> {
+    Menu {
+        enter_commits: HighlightSource::Pointer,
+        ..Menu::new(&ROWS)
+    }
+}
+
+#[test]
+fn hover_leaves_the_keyboard_highlight() {
+    let (state, _) = reduce(open(), Input::Hover(3), &Menu::new(&ROWS));
+    assert_eq!(state.highlight, 1);
+    assert_eq!(state.highlighted(HighlightSource::Keyboard), 1);
+    assert_eq!(state.highlighted(HighlightSource::Pointer), 3);
+
+    // Enter commits the keyboard highlight by default
+    let (_, effects) = reduce(state, Input::Key(Key::Enter), &Menu::new(&ROWS));
+    assert_eq!(effects, vec![Effect::Select(3)]);
+}
+
+#[test]
+fn pointer_highlight_is_committed_with_enter_commits_pointer() {
+    let (state, _) = reduce(open(), Input::Hover(3), &pointer_menu());
+    let (_, effects) = reduce(state.clone(), Input::Key(Key::Enter), &pointer_menu());
+    assert_eq!(effects, vec![Effect::Select(5)]);
+
+    // Once the pointer leaves, the keyboard highlight is committed again
+    let (state, _) = reduce(state, Input::Unhover, &pointer_menu());
+    let (_, effects) = reduce(state, Input::Key(Key::Enter), &pointer_menu());
+    assert_eq!(effects, vec![Effect::Select(3)]);
+}
+
+#[test]
+fn arrows_move_on_from_the_row_shown_highlighted() {
+    // Under the pointer, the arrows continue from the hovered row
+    let (state, _) = reduce(open(), Input::Hover(3), &pointer_menu());
+    let (state, _) = reduce(state, Input::Key(Key::ArrowUp), &pointer_menu());
+    assert_eq!(state.highlight, 1);
+    assert_eq!(state.hover, None);
+
+    // Otherwise the hover is ignored
+    let (state, _) = reduce(open(), Input::Hover(3), &Menu::new(&ROWS));
+    let (state, _) = reduce(state, Input::Key(Key::ArrowDown), &Menu::new(&ROWS));
+    assert_eq!(state.highlight, 3);
+}
+
+#[test]
+fn focus_highlights_the_selected_row() {
+    let menu = Menu {
+        selected: Some(3),
+        ..Menu::new(&ROWS)
+    };
+    let (state, _) = reduce(Interaction::default(), Input::Focus, &menu);
+    assert_eq!(state.highlight, 3);
+
+    // Unless it can't be highlighted
+    let menu = Menu {
+        selected: Some(2),
+        ..Menu::new(&ROWS)
+    };
+    let (state, _) = reduce(Interaction::default(), Input::Focus, &menu);
+    assert_eq!(state.highlight, 1);
+}
+
+#[test]
+fn keys_are_read_from_the_code() {
+    assert_eq!(
+        Key::from_dom("ArrowDown", "ArrowDown"),
+        Some(Key::ArrowDown)
+    );
+    assert_eq!(Key::from_dom("NumpadEnter", "Enter"), Some(Key::Enter));
+    // Virtual keyboards send their action key without a code
+    assert_eq!(Key::from_dom("", "Enter"), Some(Key::Enter));
+    // The physical key counts, whatever the layout maps it to
+    assert_eq!(Key::from_dom("KeyE", "Enter"), None);
+    assert_eq!(Key::from_dom("Escape", "Esc"), Some(Key::Escape));
+}
+
+#[test]
+fn clamp_moves_to_the_closest_navigable_row() {
+    assert_eq!(clamp(&ROWS, 1), 1);
+    assert_eq!(clamp(&ROWS, 2), 1);
+    assert_eq!(clamp(&ROWS, 0), 1);
+    assert_eq!(clamp(&ROWS, 9), 3);
+    assert_eq!(clamp(&[RowKind::Inert], 0), 0);
+}
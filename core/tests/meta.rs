@@ -0,0 +1,78 @@
+//! Tests for option metadata (`SelectState::set_meta`)
+
+use futures::executor::block_on;
+
+use search_select_core::{
+    interaction::RowKind, OptionMeta, SelectFilter, SelectSession, SelectState, Selection,
+};
+
+fn state() -> SelectState<&'static str> {
+    SelectState::new(
+        vec!["alpha", "beta", "gamma", "delta"],
+        Selection::empty(),
+        SelectFilter::new(|item: &&str, search: &str| item.contains(search)),
+    )
+}
+
+fn pinned() -> OptionMeta {
+    OptionMeta {
+        pinned: true,
+        ..OptionMeta::default()
+    }
+}
+
+#[test]
+fn meta_is_shared_and_removed_when_empty() {
+    let state = state();
+    let other = state.clone();
+    let generation = state.generation();
+
+    state.set_meta(1, pinned());
+    assert_eq!(other.meta(1), Some(pinned()));
+    assert!(state.generation() > generation);
+
+    state.set_meta(1, OptionMeta::default());
+    assert_eq!(other.meta(1), None);
+
+    // Out of range indices are ignored
+    state.set_meta(9, pinned());
+    assert_eq!(state.meta(9), None);
+}
+
+#[test]
+fn meta_follows_reselected_options() {
+    let mut state = state();
+    state.set_meta(2, pinned());
+
+    block_on(state.replace_options_reselecting(vec!["gamma", "alpha"], |a, b| a == b));
+    assert_eq!(state.meta(0), Some(pinned()));
+    assert_eq!(state.pinned(), vec![0]);
+
+    block_on(state.replace_options(vec!["gamma"]));
+    assert_eq!(state.meta(0), None);
+}
+
+#[test]
+fn session_lists_pinned_first_and_skips_disabled() {
+    let state = state();
+    state.set_meta(2, pinned());
+    state.set_meta(
+        0,
+        OptionMeta {
+            disabled: true,
+            ..OptionMeta::default()
+        },
+    );
+
+    let session = SelectSession::new(state);
+    assert_eq!(session.visible(), vec![2, 0, 1, 3]);
+    assert_eq!(
+        session.rows(),
+        vec![
+            RowKind::Option(2),
+            RowKind::Inert,
+            RowKind::Option(1),
+            RowKind::Option(3),
+        ]
+    );
+}
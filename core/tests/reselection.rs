@@ -0,0 +1,117 @@
+//! Tests for replacing the options of a `SelectState` while keeping the selection
+
+use futures::executor::block_on;
+
+use search_select_core::{ReselectionReport, SelectDisplay, SelectFilter, SelectState, Selection};
+
+#[derive(Debug, PartialEq)]
+struct Row {
+    id: u32,
+    name: &'static str,
+}
+
+fn rows(ids: &[u32]) -> Vec<Row> {
+    ids.iter().map(|&id| Row { id, name: "row" }).collect()
+}
+
+fn state(ids: &[u32]) -> SelectState<Row> {
+    SelectState::new(
+        rows(ids),
+        Selection::empty(),
+        SelectFilter::new(|row: &Row, search: &str| row.name.contains(search)),
+    )
+}
+
+fn replace_by_id(state: &mut SelectState<Row>, ids: &[u32]) -> ReselectionReport {
+    block_on(state.replace_options_reselecting_by_key(rows(ids), |row| row.id))
+}
+
+#[test]
+fn mapping_is_identity_for_unchanged_options() {
+    let mut state = state(&[1, 2, 3]);
+    let report = replace_by_id(&mut state, &[1, 2, 3]);
+    assert_eq!(report.mapping, vec![Some(0), Some(1), Some(2)]);
+    assert_eq!(report.translate(2), Some(2));
+}
+
+#[test]
+fn mapping_follows_reordered_options() {
+    let mut state = state(&[1, 2, 3]);
+    let report = replace_by_id(&mut state, &[3, 1, 2]);
+    assert_eq!(report.mapping, vec![Some(1), Some(2), Some(0)]);
+    assert_eq!(report.translate(0), Some(1));
+    assert_eq!(report.translate(2), Some(0));
+}
+
+#[test]
+fn mapping_marks_removed_options() {
+    let mut state = state(&[1, 2, 3, 4]);
+    let report = replace_by_id(&mut state, &[4, 2, 5]);
+    assert_eq!(report.mapping, vec![None, Some(1), None, Some(0)]);
+    assert_eq!(report.translate(0), None);
+    assert_eq!(report.translate(3), Some(0));
+    // Past the old options
+    assert_eq!(report.translate(4), None);
+}
+
+#[test]
+fn mapping_is_empty_with_selection_eq() {
+    let mut state = state(&[1, 2]);
+    let report =
+        block_on(state.replace_options_reselecting(rows(&[2, 1]), |a: &Row, b| a.id == b.id));
+    assert!(report.mapping.is_empty());
+    assert_eq!(report.translate(0), None);
+}
+
+#[test]
+fn by_key_keeps_selected_keys() {
+    let mut state = state(&[1, 2, 3, 4]);
+    state.select_many(&[1, 2]);
+    let report = replace_by_id(&mut state, &[5, 3, 1]);
+    assert_eq!(report.kept, vec![1]);
+    assert_eq!(report.dropped, vec![1]);
+    assert!(state.is_selected(1));
+    assert_eq!(state.selected_items().len(), 1);
+    assert!(state.invalid_selections().is_empty());
+}
+
+#[test]
+fn by_key_moves_a_single_selection() {
+    let mut state = SelectState::new(
+        rows(&[1, 2, 3]),
+        Selection::some(2),
+        SelectFilter::new(|row: &Row, search: &str| row.name.contains(search)),
+    );
+    replace_by_id(&mut state, &[3, 4]);
+    assert_eq!(
+        state.selected_items().first().map(|(index, _)| *index),
+        Some(0)
+    );
+}
+
+#[test]
+fn by_key_uses_the_first_of_duplicate_keys() {
+    let mut state = state(&[1, 2]);
+    state.select(1);
+    let report = replace_by_id(&mut state, &[2, 2, 1]);
+    assert_eq!(report.kept, vec![0]);
+    assert_eq!(report.mapping, vec![Some(2), Some(0)]);
+}
+
+#[test]
+fn by_display_keeps_selected_labels() {
+    let mut state = SelectState::new(
+        vec!["Apple", "Banana", "Cherry"],
+        Selection::empty(),
+        SelectFilter::new(|item: &&str, search: &str| item.contains(search)),
+    );
+    state.select_many(&[0, 2]);
+    let display = SelectDisplay::new(|item: &&str| item.to_string());
+    let report = block_on(
+        state.replace_options_reselecting_by_display(vec!["Cherry", "Date", "Apple"], &display),
+    );
+    assert_eq!(report.kept, vec![2, 0]);
+    assert!(report.dropped.is_empty());
+    assert!(state.is_selected(0) && state.is_selected(2));
+    assert!(!state.is_selected(1));
+}
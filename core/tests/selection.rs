@@ -0,0 +1,305 @@
+//! Tests for building a `Selection` from values, and property tests for the
+//! selection invariants of `SelectState`. Run with `cargo test -p search-select-core`
+
+use std::{cell::Cell, rc::Rc};
+
+use futures::executor::block_on;
+use proptest::prelude::*;
+
+use search_select_core::{SelectFilter, SelectState, Selection};
+
+/// Largest option set generated (indices are drawn a bit past it, to hit the bounds checks)
+const MAX_OPTIONS: usize = 16;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Select(usize),
+    Deselect(usize),
+    Clear,
+    SelectMany(Vec<usize>),
+    DeselectMany(Vec<usize>),
+    SetSelection(Vec<usize>),
+    Replace(Vec<u8>),
+    Reselect(Vec<u8>),
+    ReselectByKey(Vec<u8>),
+}
+
+fn index() -> impl Strategy<Value = usize> {
+    0..MAX_OPTIONS + 4
+}
+
+fn options() -> impl Strategy<Value = Vec<u8>> {
+    // Few distinct values, so reselecting finds (and collides on) equal options
+    prop::collection::vec(0..8u8, 0..=MAX_OPTIONS)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        index().prop_map(Op::Select),
+        index().prop_map(Op::Deselect),
+        Just(Op::Clear),
+        prop::collection::vec(index(), 0..6).prop_map(Op::SelectMany),
+        prop::collection::vec(index(), 0..6).prop_map(Op::DeselectMany),
+        prop::collection::vec(index(), 0..6).prop_map(Op::SetSelection),
+        options().prop_map(Op::Replace),
+        options().prop_map(Op::Reselect),
+        options().prop_map(Op::ReselectByKey),
+    ]
+}
+
+/// An empty selection of each kind
+fn selection() -> impl Strategy<Value = Selection> {
+    prop_oneof![
+        Just(Selection::one(0)),
+        Just(Selection::none()),
+        Just(Selection::empty()),
+    ]
+}
+
+fn state(options: Vec<u8>, selection: Selection) -> SelectState<u8> {
+    SelectState::new(
+        options,
+        selection,
+        SelectFilter::new(|item: &u8, search: &str| item.to_string().contains(search)),
+    )
+}
+
+/// Every selected index, including dangling ones
+fn selected(state: &SelectState<u8>) -> Vec<usize> {
+    let mut indices = state
+        .selected_items()
+        .into_iter()
+        .map(|(index, _)| index)
+        .chain(state.invalid_selections())
+        .collect::<Vec<_>>();
+    indices.sort_unstable();
+    indices
+}
+
+/// Rebuild `selection` (of the same kind) from `indices`
+fn with_indices(selection: &Selection, indices: Vec<usize>) -> Selection {
+    match selection {
+        Selection::AlwaysOne(_) => Selection::one(indices.first().copied().unwrap_or_default()),
+        Selection::MaybeOne(_) => Selection::MaybeOne(indices.first().copied()),
+        Selection::Multiple(_) => Selection::multiple(indices),
+    }
+}
+
+fn apply(state: &mut SelectState<u8>, kind: &Selection, op: Op) {
+    match op {
+        Op::Select(index) => {
+            state.select(index);
+        }
+        Op::Deselect(index) => {
+            state.deselect(index);
+        }
+        Op::Clear => {
+            state.clear();
+        }
+        Op::SelectMany(indices) => {
+            state.select_many(&indices);
+        }
+        Op::DeselectMany(indices) => {
+            state.deselect_many(&indices);
+        }
+        Op::SetSelection(indices) => {
+            state.set_selection(with_indices(kind, indices));
+        }
+        Op::Replace(options) => block_on(state.replace_options(options)),
+        Op::Reselect(options) => {
+            block_on(state.replace_options_reselecting(options, |a, b| a == b));
+        }
+        Op::ReselectByKey(options) => {
+            block_on(state.replace_options_reselecting_by_key(options, |item| *item));
+        }
+    }
+}
+
+#[test]
+fn some_value_selects_first_equal_option() {
+    let options = ["a", "b", "a"];
+    assert_eq!(
+        Selection::some_value(&options, &"a"),
+        Selection::MaybeOne(Some(0))
+    );
+    assert_eq!(
+        Selection::some_value(&options, &"b"),
+        Selection::MaybeOne(Some(1))
+    );
+    assert_eq!(Selection::some_value(&options, &"z"), Selection::none());
+    assert_eq!(
+        Selection::some_value(&[] as &[&str], &"a"),
+        Selection::none()
+    );
+}
+
+#[test]
+fn multiple_values_skips_missing_values() {
+    let options = ["a", "b", "c", "b"];
+    assert_eq!(
+        Selection::multiple_values(&options, &["c", "z", "a"]),
+        Selection::multiple(vec![0, 2])
+    );
+    assert_eq!(
+        Selection::multiple_values(&options, &["b", "b"]),
+        Selection::multiple(vec![1])
+    );
+    assert_eq!(
+        Selection::multiple_values(&options, &[] as &[&str]),
+        Selection::empty()
+    );
+}
+
+/// Count the notifications sent to subscribers of `state`
+fn notifications(state: &SelectState<u8>) -> Rc<Cell<usize>> {
+    let count = Rc::new(Cell::new(0));
+    state.subscribe({
+        let count = count.clone();
+        move || count.set(count.get() + 1)
+    });
+    count
+}
+
+proptest! {
+    #[test]
+    fn select_then_deselect_restores_selection(
+        options in options(),
+        initial in prop::collection::vec(index(), 0..6),
+        index in index(),
+    ) {
+        let state = state(options, Selection::empty());
+        state.select_many(&initial);
+        let before = selected(&state);
+        prop_assume!(!before.contains(&index));
+
+        state.select(index);
+        state.deselect(index);
+        prop_assert_eq!(selected(&state), before);
+    }
+
+    #[test]
+    fn select_then_deselect_restores_nullable_selection(
+        options in options(),
+        index in index(),
+    ) {
+        let state = state(options, Selection::none());
+        state.select(index);
+        state.deselect(index);
+        prop_assert!(selected(&state).is_empty());
+    }
+
+    #[test]
+    fn indices_stay_within_options(
+        options in options(),
+        kind in selection(),
+        ops in prop::collection::vec(op(), 0..24),
+    ) {
+        let mut state = state(options, kind.clone());
+        for op in ops {
+            apply(&mut state, &kind, op);
+            // `AlwaysOne` can't be empty, so it points at 0 until there is an option there
+            let allowed: &[usize] = match kind {
+                Selection::AlwaysOne(_) if state.iter().len() == 0 => &[0],
+                _ => &[],
+            };
+            prop_assert_eq!(
+                state.invalid_selections(),
+                allowed,
+                "dangling selection over {} options",
+                state.iter().len()
+            );
+        }
+    }
+
+    #[test]
+    fn reselecting_keeps_equal_options_selected(
+        options in options(),
+        new_options in options(),
+        indices in prop::collection::vec(index(), 0..6),
+    ) {
+        let mut state = state(options, Selection::empty());
+        state.select_many(&indices);
+        let values = state
+            .selected_items()
+            .into_iter()
+            .map(|(_, item)| *item)
+            .collect::<Vec<_>>();
+
+        let report =
+            block_on(state.replace_options_reselecting(new_options.clone(), |a, b| a == b));
+        prop_assert!(state.invalid_selections().is_empty());
+        prop_assert!(report.kept.iter().all(|&index| index < new_options.len()));
+        prop_assert_eq!(report.kept.len() + report.dropped.len(), values.len());
+
+        // Every previously selected value that still exists is selected (at its first position)
+        for value in values {
+            if let Some(position) = new_options.iter().position(|option| *option == value) {
+                prop_assert!(state.is_selected(position));
+            }
+        }
+    }
+
+    #[test]
+    fn select_many_matches_repeated_select(
+        options in options(),
+        kind in selection(),
+        indices in prop::collection::vec(index(), 0..6),
+    ) {
+        let batched = state(options.clone(), kind.clone());
+        let looped = state(options, kind);
+        let notified = notifications(&batched);
+
+        let changed = batched.select_many(&indices);
+        let looped_changed = indices
+            .iter()
+            .fold(false, |changed, &index| looped.select(index) | changed);
+        prop_assert_eq!(changed, looped_changed);
+        prop_assert_eq!(selected(&batched), selected(&looped));
+        // The first pick also notifies when it only ends the pristine state
+        prop_assert!(notified.get() <= 1);
+        prop_assert!(!changed || notified.get() == 1);
+    }
+
+    #[test]
+    fn deselect_many_matches_repeated_deselect(
+        options in options(),
+        kind in selection(),
+        initial in prop::collection::vec(index(), 0..6),
+        indices in prop::collection::vec(index(), 0..6),
+    ) {
+        let batched = state(options.clone(), kind.clone());
+        let looped = state(options, kind);
+        batched.select_many(&initial);
+        looped.select_many(&initial);
+        let notified = notifications(&batched);
+
+        let changed = batched.deselect_many(&indices);
+        let looped_changed = indices
+            .iter()
+            .fold(false, |changed, &index| looped.deselect(index) | changed);
+        prop_assert_eq!(changed, looped_changed);
+        prop_assert_eq!(selected(&batched), selected(&looped));
+        prop_assert_eq!(notified.get(), changed as usize);
+    }
+
+    #[test]
+    fn set_selection_keeps_indices_within_options(
+        options in options(),
+        initial in prop::collection::vec(index(), 0..6),
+        indices in prop::collection::vec(index(), 0..6),
+    ) {
+        let len = options.len();
+        let state = state(options, Selection::empty());
+        state.select_many(&initial);
+        let before = selected(&state);
+        let notified = notifications(&state);
+
+        let changed = state.set_selection(Selection::multiple(indices.clone()));
+        let mut expected = indices.into_iter().filter(|&i| i < len).collect::<Vec<_>>();
+        expected.sort_unstable();
+        expected.dedup();
+        prop_assert_eq!(selected(&state), expected.clone());
+        prop_assert_eq!(changed, before != expected);
+        prop_assert_eq!(notified.get(), changed as usize);
+    }
+}
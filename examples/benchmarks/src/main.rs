@@ -0,0 +1,175 @@
+//! In-browser counterpart of the native criterion benches (`core/benches`):
+//! times filtering and `filtered_items()` under wasm, where the numbers that
+//! matter for the UI are made.
+
+use yew::prelude::*;
+use yewtil::future::LinkFuture;
+
+use yew_bulma_search_select::{SelectFilter, SelectKeyFilter, SelectState, Selection};
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+/// Runs per measurement (the median is reported)
+const RUNS: usize = 15;
+const QUERY: &str = "42";
+
+fn main() {
+    yew::start_app::<Benchmarks>();
+}
+
+/// Milliseconds from the high resolution timer
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+fn median(mut samples: Vec<f64>) -> f64 {
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    samples[samples.len() / 2]
+}
+
+/// `n` numbered options, with a case-insensitive substring filter
+fn state(n: usize) -> SelectState<String> {
+    let options = (0..n).map(|i| format!("Option {}", i)).collect::<Vec<_>>();
+    SelectState::new(
+        options,
+        Selection::empty(),
+        SelectFilter::new(|item: &String, search: &str| {
+            item.to_lowercase().contains(&search.to_lowercase())
+        }),
+    )
+}
+
+/// Median timings (ms) at one option count
+pub struct Measurement {
+    options: usize,
+    filter: f64,
+    search_key: f64,
+    filtered_items: f64,
+}
+
+async fn measure(n: usize) -> Measurement {
+    let state = state(n);
+    // Every tenth option selected, so the selection lookups aren't trivial
+    state.select_many(&(0..n).step_by(10).collect::<Vec<_>>());
+
+    let mut filter = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = now();
+        state.filter(QUERY).await;
+        filter.push(now() - start);
+    }
+
+    let mut filtered_items = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = now();
+        let _ = state.filtered_items().len();
+        filtered_items.push(now() - start);
+    }
+
+    let keyed = state.with_search_key(|item: &String| item.clone(), SelectKeyFilter::contains());
+    let mut search_key = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = now();
+        keyed.filter(QUERY).await;
+        search_key.push(now() - start);
+    }
+
+    Measurement {
+        options: n,
+        filter: median(filter),
+        search_key: median(search_key),
+        filtered_items: median(filtered_items),
+    }
+}
+
+pub struct Benchmarks {
+    link: ComponentLink<Self>,
+    running: bool,
+    results: Vec<Measurement>,
+}
+
+pub enum Msg {
+    Run,
+    Done(Vec<Measurement>),
+}
+
+impl Component for Benchmarks {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            running: false,
+            results: Vec::new(),
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Run => {
+                self.running = true;
+                self.link.send_future(async {
+                    let mut results = Vec::with_capacity(SIZES.len());
+                    for &n in SIZES.iter() {
+                        results.push(measure(n).await);
+                    }
+                    Msg::Done(results)
+                });
+                true
+            }
+            Msg::Done(results) => {
+                self.running = false;
+                self.results = results;
+                true
+            }
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <main class="section">
+                <div class="container">
+                    <h1 class="title">{"Select benchmarks"}</h1>
+                    <p class="block">
+                        { format!("Median of {} runs, searching for {:?}, in milliseconds.", RUNS, QUERY) }
+                    </p>
+                    <button
+                        class=classes!("button", "is-primary", "block", if self.running { Some("is-loading") } else { None })
+                        disabled=self.running
+                        onclick=self.link.callback(|_| Msg::Run)
+                    >
+                        {"Run"}
+                    </button>
+                    <table class="table">
+                        <thead>
+                            <tr>
+                                <th>{"Options"}</th>
+                                <th>{"filter"}</th>
+                                <th>{"filter (search key)"}</th>
+                                <th>{"filtered_items()"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                        {
+                            for self.results.iter().map(|result| html! {
+                                <tr>
+                                    <td>{ result.options }</td>
+                                    <td>{ format!("{:.3}", result.filter) }</td>
+                                    <td>{ format!("{:.3}", result.search_key) }</td>
+                                    <td>{ format!("{:.3}", result.filtered_items) }</td>
+                                </tr>
+                            })
+                        }
+                        </tbody>
+                    </table>
+                </div>
+            </main>
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use yew::prelude::*;
+use yewtil::future::LinkFuture;
+
+use yew_bulma_search_select::{Select, SelectAction, SelectDisplay, SelectState, Selection};
+
+use crate::data::contains;
+
+/// Tags which can be created from the search text
+pub struct Creatable {
+    link: ComponentLink<Self>,
+    display: SelectDisplay<String>,
+    state: SelectState<String>,
+    query: String,
+}
+
+pub enum Msg {
+    Search(String),
+    Create,
+    Created(SelectState<String>),
+    Selected(usize),
+    Removed(usize),
+}
+
+impl Creatable {
+    /// Whether the search text names a tag which doesn't exist yet
+    fn can_create(&self) -> bool {
+        let query = self.query.trim();
+        !query.is_empty() && !self.state.iter().any(|tag| tag.eq_ignore_ascii_case(query))
+    }
+}
+
+impl Component for Creatable {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let tags = vec![
+            String::from("bug"),
+            String::from("documentation"),
+            String::from("enhancement"),
+        ];
+        Self {
+            link,
+            display: SelectDisplay::new(|tag: &String| tag.clone()),
+            state: SelectState::new(tags, Selection::empty(), contains()),
+            query: String::new(),
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Search(query) => {
+                self.query = query;
+                true
+            }
+            Msg::Create => {
+                if !self.can_create() {
+                    return false;
+                }
+                let mut tags = self.state.iter().cloned().collect::<Vec<_>>();
+                tags.push(self.query.trim().to_string());
+                let index = tags.len() - 1;
+
+                let mut state = self.state.clone();
+                self.link.send_future(async move {
+                    state.replace_options_reselecting(tags, |a, b| a == b).await;
+                    state.select(index);
+                    Msg::Created(state)
+                });
+                false
+            }
+            Msg::Created(state) => {
+                self.state = state;
+                self.query.clear();
+                true
+            }
+            Msg::Selected(index) => self.state.select(index),
+            Msg::Removed(index) => self.state.deselect(index),
+        }
+    }
+
+    fn view(&self) -> Html {
+        let actions = if self.can_create() {
+            vec![(
+                format!("Create \"{}\"", self.query.trim()),
+                SelectAction::from_callback(self.link.callback(|_| Msg::Create)),
+            )]
+        } else {
+            Vec::new()
+        };
+
+        html! {
+            <Select<String>
+                state=self.state.clone()
+                display=self.display.clone()
+                actions=actions
+                onsearch=self.link.callback(Msg::Search)
+                onselected=self.link.callback(Msg::Selected)
+                onremoved=self.link.callback(Msg::Removed)
+                placeholder="Search or create tags"
+            />
+        }
+    }
+}
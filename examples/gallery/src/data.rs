@@ -0,0 +1,45 @@
+use yew_bulma_search_select::{SelectDisplay, SelectFilter};
+
+/// Test data with a category, for the grouping page
+#[derive(Debug, PartialEq)]
+pub struct Food {
+    pub name: &'static str,
+    pub group: &'static str,
+}
+
+const fn food(name: &'static str, group: &'static str) -> Food {
+    Food { name, group }
+}
+
+pub const FOODS: &[Food] = &[
+    food("Apple", "Fruit"),
+    food("Banana", "Fruit"),
+    food("Cherry", "Fruit"),
+    food("Mango", "Fruit"),
+    food("Carrot", "Vegetables"),
+    food("Leek", "Vegetables"),
+    food("Potato", "Vegetables"),
+    food("Spinach", "Vegetables"),
+    food("Barley", "Grains"),
+    food("Oats", "Grains"),
+    food("Rice", "Grains"),
+];
+
+pub fn food_display() -> SelectDisplay<Food> {
+    SelectDisplay::new(|food: &Food| food.name.to_string())
+}
+
+/// Case-insensitive substring filter, for string options
+pub fn contains() -> SelectFilter<String> {
+    SelectFilter::new(|item: &String, search: &str| {
+        item.to_lowercase().contains(&search.to_lowercase())
+    })
+}
+
+/// Filter for a food's name or group
+pub fn food_filter() -> SelectFilter<Food> {
+    SelectFilter::new(|food: &Food, search: &str| {
+        let search = search.to_lowercase();
+        food.name.to_lowercase().contains(&search) || food.group.to_lowercase().contains(&search)
+    })
+}
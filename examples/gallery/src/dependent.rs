@@ -0,0 +1,124 @@
+use yew::prelude::*;
+
+use yew_bulma_search_select::{
+    Select, SelectDisplay, SelectFilter, SelectFilterWithCtx, SelectState, Selection,
+};
+
+use crate::data::{food_display, Food, FOODS};
+
+const GROUPS: &[&str] = &["Fruit", "Vegetables", "Grains"];
+
+/// A food group select, restricting the foods that can be picked in a second one
+pub struct Dependent {
+    link: ComponentLink<Self>,
+    group_display: SelectDisplay<&'static str>,
+    groups: SelectState<&'static str>,
+    food_display: SelectDisplay<Food>,
+    /// Shared with the foods state, holding the picked group as its context
+    food_filter: SelectFilterWithCtx<Food, Option<&'static str>>,
+    foods: SelectState<Food>,
+}
+
+pub enum Msg {
+    GroupPicked(usize),
+    GroupCleared(usize),
+    FoodSelected(usize),
+    FoodRemoved(usize),
+}
+
+impl Dependent {
+    /// Show only the foods of `group` (all of them for `None`), dropping any
+    /// picked foods from other groups
+    fn restrict(&self, group: Option<&'static str>) {
+        if let Some(group) = group {
+            let others = self
+                .foods
+                .selected_items()
+                .into_iter()
+                .filter(|(_, food)| food.group != group)
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            self.foods.deselect_many(&others);
+        }
+        self.food_filter.set_ctx(group);
+        self.foods.refresh_filter();
+    }
+}
+
+impl Component for Dependent {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let food_filter = SelectFilterWithCtx::new(
+            None,
+            |food: &Food, search: &str, group: &Option<&'static str>| {
+                group.map_or(true, |group| food.group == group)
+                    && food.name.to_lowercase().contains(&search.to_lowercase())
+            },
+        );
+        Self {
+            link,
+            group_display: SelectDisplay::new(|group: &&'static str| group.to_string()),
+            groups: SelectState::from_static(
+                GROUPS,
+                Selection::none(),
+                SelectFilter::new(|group: &&'static str, search: &str| {
+                    group.to_lowercase().contains(&search.to_lowercase())
+                }),
+            ),
+            food_display: food_display(),
+            foods: SelectState::from_static(FOODS, Selection::empty(), food_filter.clone()),
+            food_filter,
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::GroupPicked(index) => {
+                self.groups.select(index);
+                self.restrict(GROUPS.get(index).copied());
+            }
+            Msg::GroupCleared(index) => {
+                self.groups.deselect(index);
+                self.restrict(None);
+            }
+            Msg::FoodSelected(index) => {
+                self.foods.select(index);
+            }
+            Msg::FoodRemoved(index) => {
+                self.foods.deselect(index);
+            }
+        }
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="columns">
+                <div class="column">
+                    <label class="label">{ "Group" }</label>
+                    <Select<&'static str>
+                        state=self.groups.clone()
+                        display=self.group_display.clone()
+                        onselected=self.link.callback(Msg::GroupPicked)
+                        onremoved=self.link.callback(Msg::GroupCleared)
+                    />
+                </div>
+                <div class="column">
+                    <label class="label">{ "Foods" }</label>
+                    <Select<Food>
+                        state=self.foods.clone()
+                        display=self.food_display.clone()
+                        onselected=self.link.callback(Msg::FoodSelected)
+                        onremoved=self.link.callback(Msg::FoodRemoved)
+                    />
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use yew::prelude::*;
+
+use yew_bulma_search_select::{Select, SelectDisplay, SelectGroup, SelectState, Selection};
+
+use crate::data::{food_display, food_filter, Food, FOODS};
+
+/// Foods grouped by category, with a checkbox per group
+pub struct Grouping {
+    link: ComponentLink<Self>,
+    display: SelectDisplay<Food>,
+    group_by: SelectGroup<Food>,
+    state: SelectState<Food>,
+}
+
+pub enum Msg {
+    Selected(Vec<usize>),
+    Removed(Vec<usize>),
+}
+
+impl Component for Grouping {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            display: food_display(),
+            group_by: SelectGroup::new(|food: &Food| food.group.to_string()),
+            state: SelectState::from_static(FOODS, Selection::empty(), food_filter()),
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Selected(indices) => self.state.select_many(&indices),
+            Msg::Removed(indices) => self.state.deselect_many(&indices),
+        }
+    }
+
+    fn view(&self) -> Html {
+        let selected = self
+            .state
+            .selected_items()
+            .into_iter()
+            .map(|(_, food)| food.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        html! {
+            <>
+                <Select<Food>
+                    state=self.state.clone()
+                    display=self.display.clone()
+                    group_by=Some(self.group_by.clone())
+                    checkboxes=true
+                    onselectedmany=self.link.callback(Msg::Selected)
+                    onremovedmany=self.link.callback(Msg::Removed)
+                />
+                <p class="help">{ format!("Selected: {}", selected) }</p>
+            </>
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use yew::prelude::*;
+
+use yew_bulma_search_select::{Select, SelectDisplay, SelectMetrics, SelectState, Selection};
+
+use crate::data::contains;
+
+const OPTIONS: usize = 100_000;
+/// Options rendered before the "Show more" row
+const MAX_RESULTS: usize = 50;
+
+/// A select over many options, which only renders the first matches
+pub struct Large {
+    link: ComponentLink<Self>,
+    display: SelectDisplay<String>,
+    state: SelectState<String>,
+    metrics: Option<SelectMetrics>,
+}
+
+pub enum Msg {
+    Selected(usize),
+    Metrics(SelectMetrics),
+}
+
+impl Component for Large {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let options = (0..OPTIONS)
+            .map(|i| format!("Item {:06}", i))
+            .collect::<Vec<_>>();
+        Self {
+            link,
+            display: SelectDisplay::new(|item: &String| item.clone()),
+            state: SelectState::new(options, Selection::none(), contains()),
+            metrics: None,
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Selected(index) => self.state.select(index),
+            Msg::Metrics(metrics) => {
+                let changed = self.metrics.as_ref().map_or(true, |last| {
+                    last.filter_duration != metrics.filter_duration
+                        || last.rendered_items != metrics.rendered_items
+                });
+                self.metrics = Some(metrics);
+                // Re-rendering for the render count alone would never settle
+                changed
+            }
+        }
+    }
+
+    fn view(&self) -> Html {
+        let metrics = match self.metrics {
+            Some(ref metrics) => format!(
+                "Last filter: {}, rendered options: {}",
+                metrics
+                    .filter_duration
+                    .map_or_else(|| String::from("-"), |ms| format!("{:.1} ms", ms)),
+                metrics.rendered_items
+            ),
+            None => String::new(),
+        };
+
+        html! {
+            <>
+                <Select<String>
+                    state=self.state.clone()
+                    display=self.display.clone()
+                    max_results=MAX_RESULTS
+                    onselected=self.link.callback(Msg::Selected)
+                    onmetrics=self.link.callback(Msg::Metrics)
+                />
+                <p class="help">{ metrics }</p>
+            </>
+        }
+    }
+}
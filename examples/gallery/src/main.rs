@@ -0,0 +1,157 @@
+use yew::prelude::*;
+
+use yew_bulma_search_select::SelectPlayground;
+
+mod creatable;
+mod data;
+mod dependent;
+mod grouping;
+mod large;
+mod remote;
+mod typed;
+mod validation;
+
+fn main() {
+    yew::start_app::<Gallery>();
+}
+
+/// A page of the gallery, each showing one select setup end to end
+#[derive(Clone, Copy, PartialEq)]
+pub enum Page {
+    Remote,
+    Grouping,
+    Dependent,
+    Creatable,
+    Validation,
+    Typed,
+    Large,
+    Playground,
+}
+
+impl Page {
+    const ALL: [Page; 8] = [
+        Page::Remote,
+        Page::Grouping,
+        Page::Dependent,
+        Page::Creatable,
+        Page::Validation,
+        Page::Typed,
+        Page::Large,
+        Page::Playground,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Page::Remote => "Remote loading",
+            Page::Grouping => "Grouping",
+            Page::Dependent => "Dependent selects",
+            Page::Creatable => "Creatable tags",
+            Page::Validation => "Validation",
+            Page::Typed => "Typed values",
+            Page::Large => "Large option sets",
+            Page::Playground => "Playground",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Page::Remote => {
+                "Options are fetched (from a simulated server) as you type, using `onsearch`, \
+                 `loading` and `replace_options_reselecting`."
+            }
+            Page::Grouping => {
+                "Options grouped with `group_by`, with checkboxes to pick a whole group at once."
+            }
+            Page::Dependent => {
+                "The foods that can be picked follow the group picked first, through a \
+                 `SelectFilterWithCtx` and `refresh_filter`."
+            }
+            Page::Creatable => {
+                "Tags that can be added to the options when nothing matches, through an `actions` row."
+            }
+            Page::Validation => {
+                "A required field with a `max_selected` limit, showing `error` messages as the \
+                 selection changes."
+            }
+            Page::Typed => {
+                "A `TypedSelect` applying the selection itself and reporting food names \
+                 instead of option indices."
+            }
+            Page::Large => {
+                "100,000 options, rendering only the first `max_results` matches, with the \
+                 `onmetrics` numbers shown below."
+            }
+            Page::Playground => "Try out combinations of the select's props with the knobs.",
+        }
+    }
+}
+
+/// Tabbed gallery of select setups
+pub struct Gallery {
+    link: ComponentLink<Self>,
+    page: Page,
+}
+
+pub enum Msg {
+    Show(Page),
+}
+
+impl Component for Gallery {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            page: Page::Remote,
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Show(page) => {
+                let changed = self.page != page;
+                self.page = page;
+                changed
+            }
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <main class="section">
+                <div class="container">
+                    <h1 class="title">{"Search select gallery"}</h1>
+                    <div class="tabs">
+                        <ul>
+                        {
+                            for Page::ALL.iter().map(|&page| html! {
+                                <li class=classes!(if page == self.page { Some("is-active") } else { None })>
+                                    <a onclick=self.link.callback(move |_| Msg::Show(page))>{ page.title() }</a>
+                                </li>
+                            })
+                        }
+                        </ul>
+                    </div>
+                    <p class="block">{ self.page.description() }</p>
+                    {
+                        match self.page {
+                            Page::Remote => html! { <remote::Remote /> },
+                            Page::Grouping => html! { <grouping::Grouping /> },
+                            Page::Dependent => html! { <dependent::Dependent /> },
+                            Page::Creatable => html! { <creatable::Creatable /> },
+                            Page::Validation => html! { <validation::Validation /> },
+                            Page::Typed => html! { <typed::Typed /> },
+                            Page::Large => html! { <large::Large /> },
+                            Page::Playground => html! { <SelectPlayground /> },
+                        }
+                    }
+                </div>
+            </main>
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use gloo_timers::future::TimeoutFuture;
+use yew::prelude::*;
+use yewtil::future::LinkFuture;
+
+use yew_bulma_search_select::{
+    presets::{Country, COUNTRIES},
+    Select, SelectDisplay, SelectFilter, SelectState, Selection,
+};
+
+/// Simulated latency of the server
+const LATENCY_MS: u32 = 400;
+
+/// Countries fetched from a (simulated) server as the query changes
+pub struct Remote {
+    link: ComponentLink<Self>,
+    display: SelectDisplay<Country>,
+    state: SelectState<Country>,
+    loading: bool,
+    /// Incremented for each search, so late responses to older searches are dropped
+    request: usize,
+}
+
+pub enum Msg {
+    Search(String),
+    Loaded(usize, SelectState<Country>),
+    Selected(usize),
+}
+
+/// The server side of the search
+fn search(query: &str) -> Vec<Country> {
+    let query = query.to_lowercase();
+    COUNTRIES
+        .iter()
+        .filter(|country| country.name.to_lowercase().contains(&query))
+        .copied()
+        .collect()
+}
+
+impl Component for Remote {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            display: SelectDisplay::country(),
+            // The server already filtered the options, so accept all of them
+            state: SelectState::new(
+                Vec::new(),
+                Selection::none(),
+                SelectFilter::new(|_: &Country, _: &str| true),
+            ),
+            loading: false,
+            request: 0,
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Search(query) => {
+                self.request += 1;
+                let request = self.request;
+                self.loading = true;
+
+                let mut state = self.state.clone();
+                self.link.send_future(async move {
+                    TimeoutFuture::new(LATENCY_MS).await;
+                    let results = search(&query);
+                    // Keep the selected country, if the new results still have it
+                    state
+                        .replace_options_reselecting(results, |a, b| a.code == b.code)
+                        .await;
+                    Msg::Loaded(request, state)
+                });
+                true
+            }
+            Msg::Loaded(request, state) => {
+                if request != self.request {
+                    return false;
+                }
+                self.state = state;
+                self.loading = false;
+                true
+            }
+            Msg::Selected(index) => self.state.select(index),
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="field">
+                <label class="label">{"Country"}</label>
+                <div class="control">
+                    <Select<Country>
+                        state=self.state.clone()
+                        display=self.display.clone()
+                        loading=self.loading
+                        onsearch=self.link.callback(Msg::Search)
+                        onselected=self.link.callback(Msg::Selected)
+                        placeholder="Type to search the server"
+                    />
+                </div>
+            </div>
+        }
+    }
+}
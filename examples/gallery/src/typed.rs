@@ -0,0 +1,65 @@
+use yew::prelude::*;
+
+use yew_bulma_search_select::{SelectDisplay, SelectState, SelectValue, Selection, TypedSelect};
+
+use crate::data::{food_display, food_filter, Food, FOODS};
+
+/// A select reporting food names instead of option indices
+pub struct Typed {
+    link: ComponentLink<Self>,
+    display: SelectDisplay<Food>,
+    state: SelectState<Food>,
+    value_of: SelectValue<Food, &'static str>,
+    /// Changes reported by the callbacks, most recent last
+    log: Vec<String>,
+}
+
+pub enum Msg {
+    Selected(&'static str),
+    Removed(&'static str),
+}
+
+impl Component for Typed {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            display: food_display(),
+            state: SelectState::from_static(FOODS, Selection::empty(), food_filter()),
+            value_of: SelectValue::new(|food: &Food| food.name),
+            log: Vec::new(),
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        // The select already updated the state, so this only records the change
+        self.log.push(match msg {
+            Msg::Selected(name) => format!("Selected {}", name),
+            Msg::Removed(name) => format!("Removed {}", name),
+        });
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <>
+                <TypedSelect<Food, &'static str>
+                    state=self.state.clone()
+                    display=self.display.clone()
+                    value_of=self.value_of.clone()
+                    onselected=self.link.callback(Msg::Selected)
+                    onremoved=self.link.callback(Msg::Removed)
+                />
+                <ul class="help">
+                    { for self.log.iter().rev().take(5).map(|entry| html! { <li>{ entry }</li> }) }
+                </ul>
+            </>
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use yew::prelude::*;
+
+use yew_bulma_search_select::{SelectDisplay, SelectField, SelectState, Selection};
+
+use crate::data::contains;
+
+/// Most languages that can be picked
+const MAX_LANGUAGES: usize = 3;
+
+/// A required multiple select, validated as the selection changes
+pub struct Validation {
+    link: ComponentLink<Self>,
+    display: SelectDisplay<String>,
+    state: SelectState<String>,
+    /// Only complain about an empty selection once the user changed it
+    touched: bool,
+}
+
+pub enum Msg {
+    Selected(usize),
+    Removed(usize),
+}
+
+impl Validation {
+    fn error(&self) -> Option<String> {
+        // `max_selected` blocks picks past the limit, so only the minimum is checked here
+        if self.touched && self.state.selected_items().is_empty() {
+            Some(String::from("Pick at least one language"))
+        } else {
+            None
+        }
+    }
+}
+
+impl Component for Validation {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let languages = [
+            "English", "French", "German", "Italian", "Japanese", "Spanish",
+        ]
+        .iter()
+        .map(|language| language.to_string())
+        .collect::<Vec<_>>();
+        Self {
+            link,
+            display: SelectDisplay::new(|language: &String| language.clone()),
+            state: SelectState::new(languages, Selection::empty(), contains()),
+            touched: false,
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        self.touched = true;
+        match msg {
+            Msg::Selected(index) => self.state.select(index),
+            Msg::Removed(index) => self.state.deselect(index),
+        };
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <SelectField<String>
+                label="Languages"
+                state=self.state.clone()
+                display=self.display.clone()
+                help=html! { {"Required"} }
+                error=self.error()
+                show_selected_count=true
+                max_selected=MAX_LANGUAGES
+                onselected=self.link.callback(Msg::Selected)
+                onremoved=self.link.callback(Msg::Removed)
+            />
+        }
+    }
+}
@@ -1,13 +1,15 @@
+use std::sync::Arc;
+
 use yew::prelude::*;
 
-use yew_bulma_search_select::{Select, SelectDisplay, SelectFilter, SelectState, Selection};
+use yew_bulma_search_select::{SelectDisplay, SelectField, SelectFilter, SelectState, Selection};
 
 fn main() {
     yew::start_app::<App>();
 }
 
-/// Test data struct
-#[derive(Clone, PartialEq)]
+/// Test data struct (doesn't need to be `Clone`, since options are shared through an `Arc`)
+#[derive(PartialEq)]
 pub struct Data {
     name: String,
     value: u32,
@@ -46,7 +48,7 @@ impl Component for App {
     type Properties = ();
 
     fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let test_data = vec![
+        let test_data: Arc<[Data]> = vec![
             Data {
                 name: String::from("First"),
                 value: 0,
@@ -71,7 +73,8 @@ impl Component for App {
                 name: String::from("Something else with \"first\""),
                 value: 5,
             },
-        ];
+        ]
+        .into();
 
         let filter = SelectFilter::new(|item: &Data, search: &str| -> bool {
             item.name
@@ -109,49 +112,33 @@ impl Component for App {
     fn view(&self) -> Html {
         html! {
             <main>
-                <div class="field">
-                    <label class="label">{"Select Single, Non-Nullable Field"}</label>
-                    <div class="control">
-                        <Select<Data>
-                            state=self.a_data.clone()
-                            display=self.select_display.clone()
-                            onselected=self.link.callback(Msg::SelectedA)
-                        />
-                    </div>
-                </div>
-                <div class="field">
-                    <label class="label">{"Select Single, Nullable Field"}</label>
-                    <div class="control">
-                        <Select<Data>
-                            state=self.b_data.clone()
-                            display=self.select_display.clone()
-                            onselected=self.link.callback(Msg::SelectedB)
-                        />
-                    </div>
-                </div>
-                <div class="field">
-                    <label class="label">{"Select Multiple Fields"}</label>
-                    <div class="control">
-                        <Select<Data>
-                            state=self.c_data.clone()
-                            display=self.select_display.clone()
-                            onselected=self.link.callback(Msg::SelectedC)
-                            onremoved=self.link.callback(Msg::ClearedC)
-                        />
-                    </div>
-                </div>
-                <div class="field">
-                    <label class="label">{"Select Multiple Fields (Clone, omit selections from options)"}</label>
-                    <div class="control">
-                        <Select<Data>
-                            omit_selected={true}
-                            state=self.c_data.clone()
-                            display=self.select_display.clone()
-                            onselected=self.link.callback(Msg::SelectedC)
-                            onremoved=self.link.callback(Msg::ClearedC)
-                        />
-                    </div>
-                </div>
+                <SelectField<Data>
+                    label="Select Single, Non-Nullable Field"
+                    state=self.a_data.clone()
+                    display=self.select_display.clone()
+                    onselected=self.link.callback(Msg::SelectedA)
+                />
+                <SelectField<Data>
+                    label="Select Single, Nullable Field"
+                    state=self.b_data.clone()
+                    display=self.select_display.clone()
+                    onselected=self.link.callback(Msg::SelectedB)
+                />
+                <SelectField<Data>
+                    label="Select Multiple Fields"
+                    state=self.c_data.clone()
+                    display=self.select_display.clone()
+                    onselected=self.link.callback(Msg::SelectedC)
+                    onremoved=self.link.callback(Msg::ClearedC)
+                />
+                <SelectField<Data>
+                    label="Select Multiple Fields (Clone, omit selections from options)"
+                    omit_selected={true}
+                    state=self.c_data.clone()
+                    display=self.select_display.clone()
+                    onselected=self.link.callback(Msg::SelectedC)
+                    onremoved=self.link.callback(Msg::ClearedC)
+                />
                 <div class="field">
                     <label class="label">{"Dropdown for comparison"}</label>
                     <div class="control">
@@ -0,0 +1,50 @@
+//! Browser autofill of the search input
+
+use wasm_bindgen::JsCast;
+use web_sys::{Event, InputEvent};
+
+/// `autocomplete` tokens of fields commonly picked with a select, which
+/// browsers can fill in from the user's saved details (see the `autofill` prop)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Autofill {
+    /// ISO 3166-1 alpha-2 code (e.g. `"NZ"`), `country`
+    Country,
+    /// Country name, `country-name`
+    CountryName,
+    /// Preferred language, as a BCP 47 tag (e.g. `"en-NZ"`), `language`
+    Language,
+    /// Title such as "Mr" or "Dr", `honorific-prefix`
+    HonorificPrefix,
+    /// State, province or region, `address-level1`
+    Region,
+    /// Currency the user prefers to pay in (e.g. `"NZD"`), `transaction-currency`
+    Currency,
+    /// Gender identity, `sex`
+    Sex,
+}
+
+impl Autofill {
+    /// Value of the `autocomplete` attribute
+    pub fn token(self) -> &'static str {
+        match self {
+            Autofill::Country => "country",
+            Autofill::CountryName => "country-name",
+            Autofill::Language => "language",
+            Autofill::HonorificPrefix => "honorific-prefix",
+            Autofill::Region => "address-level1",
+            Autofill::Currency => "transaction-currency",
+            Autofill::Sex => "sex",
+        }
+    }
+}
+
+/// The `input` event came from the browser filling in the field rather than
+/// from typing: Chrome fires a plain `Event`, Firefox and Safari an
+/// `InputEvent` replacing the text. Scripts can fire plain `Event`s too, so
+/// this is only checked with the `autofill` prop set.
+pub(crate) fn is_autofill(event: &Event) -> bool {
+    match event.dyn_ref::<InputEvent>() {
+        Some(event) => event.input_type() == "insertReplacementText",
+        None => true,
+    }
+}
@@ -0,0 +1,46 @@
+//! Minimal access to the async Clipboard API (`navigator.clipboard`)
+
+/// Write text to the clipboard. Failures (e.g. missing permissions) are ignored.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn write_text(text: &str) {
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    // Looked up dynamically, since the Clipboard bindings are still unstable in web-sys
+    let write = || -> Result<(), JsValue> {
+        let navigator = web_sys::window()
+            .ok_or_else(|| JsValue::from_str("no window"))?
+            .navigator();
+        let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))?;
+        let write_text =
+            Reflect::get(&clipboard, &JsValue::from_str("writeText"))?.dyn_into::<Function>()?;
+        write_text.call1(&clipboard, &JsValue::from_str(text))?;
+        Ok(())
+    };
+    let _ = write();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write_text(_text: &str) {}
+
+/// Text from a `paste` event's clipboard data, if any
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn pasted_text(event: &web_sys::Event) -> Option<String> {
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let data = Reflect::get(event, &JsValue::from_str("clipboardData")).ok()?;
+    let get_data = Reflect::get(&data, &JsValue::from_str("getData"))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+    get_data
+        .call1(&data, &JsValue::from_str("text"))
+        .ok()?
+        .as_string()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn pasted_text(_event: &web_sys::Event) -> Option<String> {
+    None
+}
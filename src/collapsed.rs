@@ -0,0 +1,42 @@
+//! Collapsed group headers, optionally persisted in `sessionStorage`
+
+/// Storage key of the collapsed groups for a `persist_key`
+#[cfg(target_arch = "wasm32")]
+fn storage_key(persist_key: &str) -> String {
+    format!("{}-collapsed-groups", persist_key)
+}
+
+/// Names of the groups collapsed under `persist_key` in this browser session.
+/// Failures (e.g. storage being disabled) give no collapsed groups.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn load(persist_key: &str) -> Vec<String> {
+    web_sys::window()
+        .and_then(|window| window.session_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(&storage_key(persist_key)).ok().flatten())
+        .map(|stored| {
+            stored
+                .lines()
+                .filter(|group| !group.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn load(_persist_key: &str) -> Vec<String> {
+    Vec::new()
+}
+
+/// Store `collapsed` under `persist_key`. Failures (e.g. a full quota) are ignored.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn store(persist_key: &str, collapsed: &[String]) {
+    if let Some(storage) =
+        web_sys::window().and_then(|window| window.session_storage().ok().flatten())
+    {
+        let _ = storage.set_item(&storage_key(persist_key), &collapsed.join("\n"));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn store(_persist_key: &str, _collapsed: &[String]) {}
@@ -0,0 +1,123 @@
+use yew::prelude::*;
+
+use crate::{Select, SelectDisplay, SelectImage, SelectState, Theme};
+
+/// A [`Select`] preset for color pickers: each option carries a hex color,
+/// shown as a swatch beside its label. The dropdown lays the options out in a
+/// grid, and the selected color is shown as a swatch in the trigger.
+pub struct ColorSelect<T: 'static> {
+    props: ColorSelectProps<T>,
+    swatch: SelectImage<T>,
+}
+
+#[derive(Properties)]
+pub struct ColorSelectProps<T: 'static> {
+    pub state: SelectState<T>,
+    pub display: SelectDisplay<T>,
+    /// CSS color of each option (e.g. `"#00d1b2"`)
+    pub color: SelectDisplay<T>,
+
+    #[prop_or_default]
+    pub onselected: Option<Callback<usize>>,
+    #[prop_or_default]
+    pub onremoved: Option<Callback<usize>>,
+
+    #[prop_or_else(|| String::from("Type to search"))]
+    pub placeholder: String,
+    #[prop_or_default]
+    pub readonly: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+// This SHOULD be the auto impl, but for some reason that thinks that T needs to be Clone
+impl<T> Clone for ColorSelectProps<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            display: self.display.clone(),
+            color: self.color.clone(),
+
+            onselected: self.onselected.clone(),
+            onremoved: self.onremoved.clone(),
+
+            placeholder: self.placeholder.clone(),
+            readonly: self.readonly,
+            disabled: self.disabled,
+        }
+    }
+}
+
+impl<T> PartialEq for ColorSelectProps<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+            && self.display == other.display
+            && self.color == other.color
+            && self.onselected == other.onselected
+            && self.onremoved == other.onremoved
+            && self.placeholder == other.placeholder
+            && self.readonly == other.readonly
+            && self.disabled == other.disabled
+    }
+}
+
+impl Theme {
+    /// The Bulma theme, with the dropdown options laid out in a grid
+    pub fn bulma_grid() -> Self {
+        Self {
+            content: classes!(
+                "dropdown-content",
+                "columns",
+                "is-multiline",
+                "is-gapless",
+                "is-mobile"
+            ),
+            ..Self::bulma()
+        }
+    }
+}
+
+impl<T: 'static> Component for ColorSelect<T> {
+    type Properties = ColorSelectProps<T>;
+    type Message = ();
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self {
+            swatch: SelectImage::swatch(props.color.clone()),
+            props,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            if self.props.color != props.color {
+                self.swatch = SelectImage::swatch(props.color.clone());
+            }
+            self.props = props;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <Select<T>
+                state=self.props.state.clone()
+                display=self.props.display.clone()
+                avatar=Some(self.swatch.clone())
+                theme=Theme::bulma_grid()
+                item_class=classes!("column", "is-one-third")
+                onselected=self.props.onselected.clone()
+                onremoved=self.props.onremoved.clone()
+                placeholder=self.props.placeholder.clone()
+                readonly=self.props.readonly
+                disabled=self.props.disabled
+            />
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::{cell::RefCell, rc::Rc};
+
+use web_sys::HtmlElement;
+use yew::{ComponentLink, NodeRef};
+
+use crate::{Msg, Select};
+
+/// Handle for driving a mounted [`Select`] from its parent (e.g. from tours,
+/// hotkeys or test code). Obtained through the `oncontroller` prop.
+pub struct SelectController<T: 'static> {
+    pub(crate) link: ComponentLink<Select<T>>,
+    pub(crate) input_ref: NodeRef,
+    pub(crate) query: Rc<RefCell<String>>,
+}
+
+impl<T: 'static> Clone for SelectController<T> {
+    fn clone(&self) -> Self {
+        Self {
+            link: self.link.clone(),
+            input_ref: self.input_ref.clone(),
+            query: self.query.clone(),
+        }
+    }
+}
+
+impl<T: 'static> SelectController<T> {
+    /// Open the dropdown
+    pub fn open(&self) {
+        self.link.send_message(Msg::Open);
+    }
+
+    /// Close the dropdown, clearing the search text
+    pub fn close(&self) {
+        self.link.send_message(Msg::Blur);
+    }
+
+    /// Move the browser focus to the search input (which opens the dropdown)
+    pub fn focus(&self) {
+        if let Some(input) = self.input_ref.cast::<HtmlElement>() {
+            let _ = input.focus();
+        }
+    }
+
+    /// Replace the search text, filtering the options
+    pub fn set_query(&self, query: String) {
+        self.link.send_message(Msg::Input(query));
+    }
+
+    /// Pick the option matching `value` (e.g. filled in by a password manager
+    /// or autofill script), as if the browser had autofilled it. Values
+    /// matching no option are left as search text.
+    pub fn autofill(&self, value: String) {
+        self.link.send_message(Msg::Autofill(value));
+    }
+
+    /// Search text of the select as of its last render (empty when not
+    /// searching). Unlike `SelectState::query`, this is only this select's
+    /// search when several share a state.
+    pub fn query(&self) -> String {
+        self.query.borrow().clone()
+    }
+
+    /// Highlight the row at `position` in the dropdown
+    pub fn highlight(&self, position: usize) {
+        self.link.send_message(Msg::Highlight(position));
+    }
+}
@@ -0,0 +1,212 @@
+use yew::prelude::*;
+
+use crate::{ids::unique_id, Select, SelectDisplay, SelectState};
+
+/// A [`Select`] inside the Bulma `field`/`label`/`control` scaffolding, with the
+/// label wired to the search input through a generated id.
+pub struct SelectField<T: 'static> {
+    link: ComponentLink<Self>,
+    props: SelectFieldProps<T>,
+    /// Generated id of the search input (unless `id` is given)
+    generated_id: String,
+    /// Number of selected items at the last render (for the counter)
+    selected_count: usize,
+    /// Id of the state subscription, to update the counter on changes made elsewhere
+    subscription: usize,
+}
+
+#[derive(Properties)]
+pub struct SelectFieldProps<T: 'static> {
+    pub label: String,
+    pub state: SelectState<T>,
+    pub display: SelectDisplay<T>,
+
+    /// Id of the search input (generated if not set)
+    #[prop_or_default]
+    pub id: Option<String>,
+    /// Help text under the control
+    #[prop_or_default]
+    pub help: Option<Html>,
+    /// Validation error (see `SelectProps::error`)
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Show how many items are selected under the control (e.g. "2/5 selected")
+    #[prop_or_default]
+    pub show_selected_count: bool,
+    /// Most items that can be selected (see `SelectProps::max_selected`), also
+    /// shown by the counter (which turns red if it is exceeded)
+    #[prop_or_default]
+    pub max_selected: Option<usize>,
+
+    #[prop_or_default]
+    pub onselected: Option<Callback<usize>>,
+    #[prop_or_default]
+    pub onremoved: Option<Callback<usize>>,
+    #[prop_or_default]
+    pub onselectedmany: Option<Callback<Vec<usize>>>,
+
+    #[prop_or_default]
+    pub omit_selected: bool,
+    #[prop_or_else(|| String::from("Type to search"))]
+    pub placeholder: String,
+    #[prop_or_default]
+    pub readonly: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub loading: bool,
+}
+
+// This SHOULD be the auto impl, but for some reason that thinks that T needs to be Clone
+impl<T> Clone for SelectFieldProps<T> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            state: self.state.clone(),
+            display: self.display.clone(),
+
+            id: self.id.clone(),
+            help: self.help.clone(),
+            error: self.error.clone(),
+            show_selected_count: self.show_selected_count,
+            max_selected: self.max_selected,
+
+            onselected: self.onselected.clone(),
+            onremoved: self.onremoved.clone(),
+            onselectedmany: self.onselectedmany.clone(),
+
+            omit_selected: self.omit_selected,
+            placeholder: self.placeholder.clone(),
+            readonly: self.readonly,
+            disabled: self.disabled,
+            loading: self.loading,
+        }
+    }
+}
+
+impl<T> PartialEq for SelectFieldProps<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+            && self.state == other.state
+            && self.display == other.display
+            && self.id == other.id
+            && self.help == other.help
+            && self.error == other.error
+            && self.show_selected_count == other.show_selected_count
+            && self.max_selected == other.max_selected
+            && self.onselected == other.onselected
+            && self.onremoved == other.onremoved
+            && self.onselectedmany == other.onselectedmany
+            && self.omit_selected == other.omit_selected
+            && self.placeholder == other.placeholder
+            && self.readonly == other.readonly
+            && self.disabled == other.disabled
+            && self.loading == other.loading
+    }
+}
+
+impl<T: 'static> SelectField<T> {
+    fn input_id(&self) -> String {
+        self.props
+            .id
+            .clone()
+            .unwrap_or_else(|| self.generated_id.clone())
+    }
+
+    fn subscribe(link: &ComponentLink<Self>, state: &SelectState<T>) -> usize {
+        let link = link.clone();
+        state.subscribe(move || link.send_message(()))
+    }
+
+    fn view_counter(&self) -> Html {
+        if !self.props.show_selected_count {
+            return html! {};
+        }
+
+        let count = self.props.state.selected_items().len();
+        let (text, exceeded) = match self.props.max_selected {
+            Some(max) => (format!("{}/{} selected", count, max), count > max),
+            None => (format!("{} selected", count), false),
+        };
+        html! {
+            <p class=classes!("help", "has-text-right", if exceeded { Some("is-danger") } else { None })>
+                { text }
+            </p>
+        }
+    }
+}
+
+impl<T: 'static> Component for SelectField<T> {
+    type Properties = SelectFieldProps<T>;
+    type Message = ();
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            generated_id: unique_id("field"),
+            selected_count: props.state.selected_items().len(),
+            subscription: Self::subscribe(&link, &props.state),
+            link,
+            props,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            if self.props.state != props.state {
+                self.props.state.unsubscribe(self.subscription);
+                self.subscription = Self::subscribe(&self.link, &props.state);
+            }
+            self.props = props;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        // Only the counter depends on the state (the select subscribes itself)
+        self.props.show_selected_count
+            && self.selected_count != self.props.state.selected_items().len()
+    }
+
+    fn view(&self) -> Html {
+        let id = self.input_id();
+        html! {
+            <div class="field">
+                <label class="label" for=id.clone()>{ &self.props.label }</label>
+                <div class="control">
+                    <Select<T>
+                        state=self.props.state.clone()
+                        display=self.props.display.clone()
+                        input_id=Some(id)
+                        onselected=self.props.onselected.clone()
+                        onremoved=self.props.onremoved.clone()
+                        onselectedmany=self.props.onselectedmany.clone()
+                        max_selected=self.props.max_selected
+                        error=self.props.error.clone()
+                        omit_selected=self.props.omit_selected
+                        placeholder=self.props.placeholder.clone()
+                        readonly=self.props.readonly
+                        disabled=self.props.disabled
+                        loading=self.props.loading
+                    />
+                </div>
+                {
+                    match self.props.help {
+                        Some(ref help) => html! { <p class="help">{ help.clone() }</p> },
+                        None => html! {},
+                    }
+                }
+                { self.view_counter() }
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        self.selected_count = self.props.state.selected_items().len();
+    }
+
+    fn destroy(&mut self) {
+        self.props.state.unsubscribe(self.subscription);
+    }
+}
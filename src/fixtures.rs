@@ -0,0 +1,25 @@
+//! Reusable fixture builders for tests and demos.
+
+use crate::{SelectDisplay, SelectFilter, SelectState, Selection};
+
+/// `n` option labels: `"Option 0"`, `"Option 1"`, ...
+pub fn options(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("Option {}", i)).collect()
+}
+
+/// Case-insensitive substring filter over string options
+pub fn filter() -> SelectFilter<String> {
+    SelectFilter::new(|item: &String, search: &str| {
+        item.to_lowercase().contains(&search.to_lowercase())
+    })
+}
+
+/// Display that renders string options as-is
+pub fn display() -> SelectDisplay<String> {
+    SelectDisplay::new(|item: &String| item.clone())
+}
+
+/// A state over `options(n)` using the fixture `filter()`
+pub fn state(n: usize, selection: Selection) -> SelectState<String> {
+    SelectState::new(options(n), selection, filter())
+}
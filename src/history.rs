@@ -0,0 +1,92 @@
+//! Recent search queries, optionally persisted in `localStorage`
+
+/// How many queries are remembered
+pub(crate) const LIMIT: usize = 5;
+
+/// Move `query` to the front of `history` (dropping any older copy), keeping at
+/// most `limit` queries
+pub(crate) fn remember(history: &mut Vec<String>, query: &str, limit: usize) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+    history.retain(|previous| previous != query);
+    history.insert(0, query.to_string());
+    history.truncate(limit);
+}
+
+/// Storage key of the history for a `persist_key`
+#[cfg(target_arch = "wasm32")]
+fn storage_key(persist_key: &str) -> String {
+    format!("{}-search-history", persist_key)
+}
+
+/// Queries stored under `persist_key` (most recent first). Failures (e.g.
+/// storage being disabled) give an empty history.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn load(persist_key: &str) -> Vec<String> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(&storage_key(persist_key)).ok().flatten())
+        .map(|stored| {
+            // Queries come from a single-line input, so they can't contain newlines
+            stored
+                .lines()
+                .filter(|query| !query.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn load(_persist_key: &str) -> Vec<String> {
+    Vec::new()
+}
+
+/// Store `history` under `persist_key`. Failures (e.g. a full quota) are ignored.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn store(persist_key: &str, history: &[String]) {
+    if let Some(storage) =
+        web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+    {
+        let _ = storage.set_item(&storage_key(persist_key), &history.join("\n"));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn store(_persist_key: &str, _history: &[String]) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_queries_are_not_remembered() {
+        let mut history = vec![String::from("apple")];
+        remember(&mut history, "", LIMIT);
+        remember(&mut history, "   ", LIMIT);
+        assert_eq!(history, vec!["apple"]);
+    }
+
+    #[test]
+    fn repeated_query_moves_to_the_front() {
+        let mut history = Vec::new();
+        for query in &["apple", "banana", " apple "] {
+            remember(&mut history, query, LIMIT);
+        }
+        assert_eq!(history, vec!["apple", "banana"]);
+    }
+
+    #[test]
+    fn oldest_queries_are_dropped_past_the_limit() {
+        let mut history = Vec::new();
+        for query in &["a", "b", "c", "d"] {
+            remember(&mut history, query, 3);
+        }
+        assert_eq!(history, vec!["d", "c", "b"]);
+
+        remember(&mut history, "b", 3);
+        assert_eq!(history, vec!["b", "d", "c"]);
+    }
+}
@@ -0,0 +1,10 @@
+//! Unique element ids, for wiring up ARIA and label relationships
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A new id, unique within the page (e.g. `"ybss-field-3"`)
+pub(crate) fn unique_id(kind: &str) -> String {
+    format!("ybss-{}-{}", kind, NEXT_ID.fetch_add(1, Ordering::Relaxed))
+}
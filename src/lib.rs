@@ -1,12 +1,71 @@
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Element, EventTarget, HtmlElement, HtmlInputElement, Node, ScrollIntoViewOptions,
+    ScrollLogicalPosition,
+};
 use yew::prelude::*;
 use yewtil::future::LinkFuture;
 
-mod state;
-pub use state::SelectState;
-mod selection;
-pub use selection::Selection;
+/// How long (ms) a touch must be held on an option to toggle it in multiple mode
+const LONG_PRESS_MS: f64 = 500.0;
+/// Default `z-index` of the menu, above Bulma's fixed navbars (30) and modals (40)
+const MENU_Z_INDEX: i32 = 50;
+
+pub use search_select_core::{
+    interaction::{self as core, HighlightSource},
+    EmptyState, OptionEntry, OptionMeta, ReselectionReport, SelectAction, SelectBadge, SelectDebug,
+    SelectDisplay, SelectEnum, SelectFilter, SelectFilterWithCtx, SelectGroup, SelectImage,
+    SelectKeyFilter, SelectOrder, SelectPredicate, SelectSearchKey, SelectSession, SelectState,
+    SelectValue, Selection,
+};
+
+mod autofill;
+pub use autofill::Autofill;
+mod clipboard;
+mod collapsed;
+mod color;
+pub use color::{ColorSelect, ColorSelectProps};
+mod controller;
+pub use controller::SelectController;
+mod field;
+pub use field::{SelectField, SelectFieldProps};
+mod history;
+mod ids;
+mod listener;
+mod log;
+mod metrics;
+pub use metrics::SelectMetrics;
+mod motion;
+mod paste;
+pub use paste::PasteMatch;
+mod placement;
+mod selection_view;
+pub use selection_view::{SelectionView, SelectionViewProps};
+mod suggest;
+mod theme;
+pub use theme::Theme;
+mod timeout;
+mod typed;
+pub use typed::{TypedSelect, TypedSelectProps};
 mod wrappers;
-pub use wrappers::{SelectDisplay, SelectFilter};
+pub use wrappers::{SelectEmptyView, SelectPreview};
+
+#[cfg(feature = "testing")]
+pub mod fixtures;
+#[cfg(feature = "dev-harness")]
+mod playground;
+#[cfg(feature = "dev-harness")]
+pub use playground::{SelectPlayground, SelectPlaygroundProps};
+#[cfg(feature = "presets")]
+pub use search_select_core::presets;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 /// Bulma-based selection box
 /// TODO: document
@@ -15,14 +74,111 @@ pub struct Select<T: 'static> {
     props: SelectProps<T>,
 
     focused: bool,
+    /// The search input has DOM focus (the menu may still be closed, see `open_on`)
+    has_focus: bool,
+    /// Keyboard highlight (position in the dropdown rows)
     selection_index: usize,
+    /// Row under the pointer, tracked separately from the keyboard highlight
+    hover_index: Option<usize>,
+    /// The last interaction was with the keyboard, so the trigger and the
+    /// highlighted row get `theme.focus_visible` (like the `:focus-visible`
+    /// heuristic of browsers)
+    keyboard: bool,
+    /// The pointer was pressed on the trigger, so the focus that follows isn't
+    /// from the keyboard
+    trigger_pointer_down: bool,
+    /// Row where a Shift range selection started
+    range_anchor: Option<usize>,
+    input_ref: NodeRef,
+    /// Caret (selection start and end) in the search input, restored after the
+    /// next render, since re-rendering the value can move it to the end
+    caret: Option<(u32, u32)>,
+    /// Set while the pointer is pressed inside the menu, to suppress closing on blur
+    menu_pointer_down: bool,
+    /// Window `pointerup`/`pointercancel` listener while the pointer is pressed
+    /// inside the menu, since it may be released outside of it
+    release_listener: Option<listener::WindowListener>,
+    /// Row position and start time (ms) of the touch in progress, to detect long presses
+    touch: Option<(usize, f64)>,
+    /// Reference to the highlighted row, to scroll it into view after keyboard navigation
+    highlight_ref: NodeRef,
+    scroll_to_highlight: bool,
+    /// The outer wrapper and the menu, measured to place the menu
+    dropdown_ref: NodeRef,
+    menu_ref: NodeRef,
+    /// Scrolling content of the menu (scrolled directly with `scroll_parent`)
+    content_ref: NodeRef,
+    /// The menu opens above the input (there isn't enough room below it)
+    drop_up: bool,
+    /// The menu is still shown with `menu_leave_class` after closing
+    leaving: bool,
+    /// Incremented each time the menu closes, so stale leave timers can be detected
+    leave_generation: usize,
+    /// The user prefers reduced motion (checked once, when created)
+    reduced_motion: bool,
+    /// Window scroll and resize listeners re-placing the menu while it is open
+    viewport_listener: Option<listener::WindowListener>,
     search_text: String,
+    /// Copy of `search_text` after each render, read by `SelectController::query`
+    /// (the state's query is the last search of any select sharing it)
+    shared_query: Rc<RefCell<String>>,
+    /// Dangling selections last reported through `oninvalidselection`
+    reported_invalid: Vec<usize>,
+    /// Selection last reported through `onchange` (the initial one isn't reported)
+    reported_selection: Vec<usize>,
+    /// Tag focused with the arrow keys (position among the selected tags)
+    active_tag: Option<usize>,
+    /// Id of the error message, referenced by the input's `aria-describedby`
+    error_id: String,
+    /// A pick was blocked by `max_selected` (cleared on the next interaction)
+    limit_reached: bool,
+    /// Contents of the bulk entry dialog, if open
+    bulk: Option<BulkEntry>,
+    /// Recent search queries, most recent first (with `search_history`)
+    history: Vec<String>,
+
+    /// Display strings for each option, recomputed only when the options or display change
+    labels: Vec<String>,
+    /// Group names in order of first appearance, and the group of each option
+    /// (empty unless `group_by` is set)
+    groups: Vec<String>,
+    group_of: Vec<usize>,
+    /// Names of the collapsed groups (with `collapsible_groups`)
+    collapsed: Vec<String>,
+    /// Group checkboxes from the last render, and whether each is partially
+    /// checked (`indeterminate` is a DOM property, so it's set after rendering)
+    group_checkboxes: RefCell<Vec<(NodeRef, bool)>>,
+    /// Visible `(index, selected)` pairs, tagged with the state generation they were built from
+    visible: RefCell<Option<(usize, Rc<Vec<(usize, bool)>>)>>,
+
+    /// Global indices matching the search text (`None` when not searching). Kept
+    /// per component rather than in the state, so several selects can share a
+    /// state without fighting over the query.
+    filtered: Option<Rc<Vec<usize>>>,
+    /// The "Show more" row was picked, lifting `max_results` until the search changes
+    expanded: bool,
+    /// Close labels offered when nothing matches the search (with `did_you_mean`)
+    suggestions: Vec<String>,
+    /// Incremented for each filter future, so stale results can be detected
+    filter_generation: usize,
+    /// The state's filter revision when the search last ran (see `SelectState::refresh_filter`)
+    filter_revision: usize,
+    /// The state's metadata revision when the groups were computed (see `SelectState::set_meta`)
+    meta_revision: usize,
+    /// Id of the state subscription, to re-render on changes made elsewhere
+    subscription: usize,
+
+    // Metrics reported via `onmetrics`
+    filter_duration: Option<f64>,
+    rendered_items: Cell<usize>,
+    render_count: usize,
 }
 
 #[derive(Properties)]
-pub struct SelectProps<T> {
+pub struct SelectProps<T: 'static> {
     /// Omit selected items from the dropdown list (if false, selected will be
-    /// highlighted).
+    /// highlighted). Applies in both single and multiple mode, and the empty
+    /// state, keyboard navigation and metrics only count the remaining items.
     #[prop_or_default]
     pub omit_selected: bool,
 
@@ -36,14 +192,325 @@ pub struct SelectProps<T> {
 
     pub state: SelectState<T>,
     pub display: SelectDisplay<T>,
+    /// Hover text (`title`) for options and tags. Defaults to the label, so
+    /// labels truncated by the layout can still be read in full.
+    #[prop_or_default]
+    pub tooltip: Option<SelectDisplay<T>>,
+    /// Image URL (e.g. a user's avatar) rendered as a small rounded image beside
+    /// each option and inside the selected tags
+    #[prop_or_default]
+    pub avatar: Option<SelectImage<T>>,
+    /// Text of a tag at the end of each option's row (e.g. `"12 open issues"`).
+    /// A badge set through `SelectState::set_meta` takes precedence.
+    #[prop_or_default]
+    pub badge: Option<SelectBadge<T>>,
+    /// Details of the highlighted (or hovered) option, shown in a panel beside
+    /// the dropdown (e.g. to tell similar templates apart)
+    #[prop_or_default]
+    pub preview: Option<SelectPreview<T>>,
+
+    /// Classes used to render each part of the select (defaults to Bulma)
+    #[prop_or_default]
+    pub theme: Theme,
+    /// Extra classes for the outer dropdown wrapper
+    #[prop_or_default]
+    pub class: Classes,
+    /// Extra classes for the dropdown menu
+    #[prop_or_default]
+    pub menu_class: Classes,
+    /// Extra classes for each option row in the dropdown
+    #[prop_or_default]
+    pub item_class: Classes,
+    /// Scrolling container the select sits in (e.g. a modal body). The menu
+    /// opens upwards when it doesn't fit below the input inside this container
+    /// (rather than the window), and keyboard navigation only scrolls this
+    /// container to reveal the highlighted option.
+    #[prop_or_default]
+    pub scroll_parent: Option<NodeRef>,
+    /// `z-index` of the menu. Defaults to one above Bulma's fixed navbars and
+    /// modals; `None` leaves it to the stylesheet (Bulma's dropdowns use 20).
+    #[prop_or(Some(MENU_Z_INDEX))]
+    pub menu_z_index: Option<i32>,
+    /// Extra classes for the menu while it is open (e.g. one running a fade-in
+    /// animation, which plays each time the menu opens)
+    #[prop_or_default]
+    pub menu_enter_class: Classes,
+    /// Extra classes for the menu once it closes. If set, the menu stays shown
+    /// with them for `menu_leave_ms`, so it can fade or slide out.
+    #[prop_or_default]
+    pub menu_leave_class: Classes,
+    /// How long (ms) the menu stays shown with `menu_leave_class` after closing
+    #[prop_or(150)]
+    pub menu_leave_ms: u32,
+    /// Open and close the menu without the enter/leave classes (also the case
+    /// when the user prefers reduced motion)
+    #[prop_or_default]
+    pub disable_animations: bool,
+    /// Extra classes for the selected item tags (in multiple mode)
+    #[prop_or_default]
+    pub tag_class_extra: Classes,
+    /// Classes for the highlighted option row, overriding `theme.active_item`
+    /// (e.g. to adapt the highlight to a dark theme)
+    #[prop_or_default]
+    pub active_item_class: Option<Classes>,
+    /// Classes for selected option rows, overriding `theme.selected_item`
+    #[prop_or_default]
+    pub selected_item_class: Option<Classes>,
+
+    /// Prefix for `data-testid` attributes stamped on the trigger, input, menu,
+    /// items and tags (e.g. `"{test_id}-item-3"`), for targeting in E2E tests.
+    #[prop_or_default]
+    pub test_id: Option<String>,
+    /// Print the state's `debug_snapshot` (and the component's own search and
+    /// highlight) under the select, to copy into bug reports
+    #[prop_or_default]
+    pub debug: bool,
 
     #[prop_or_default]
     pub onselected: Option<Callback<usize>>,
     #[prop_or_default]
     pub onremoved: Option<Callback<usize>>,
+    /// Receives several indices to select at once (e.g. from a Shift range
+    /// selection), to be applied with `SelectState::select_many`. If not set,
+    /// `onselected` is emitted for each index instead.
+    #[prop_or_default]
+    pub onselectedmany: Option<Callback<Vec<usize>>>,
+    /// Receives several indices to remove at once (e.g. when a group is
+    /// deselected), to be applied with `SelectState::deselect_many`. If not set,
+    /// `onremoved` is emitted for each index instead.
+    #[prop_or_default]
+    pub onremovedmany: Option<Callback<Vec<usize>>>,
+    /// Custom content rendered at the bottom of the dropdown menu. It can be
+    /// freely clicked without the menu closing.
+    #[prop_or_default]
+    pub footer: Option<Html>,
+
+    /// Most options that can be selected (multiple mode only). Further picks
+    /// are blocked with a notice in the dropdown, which is also announced to
+    /// screen readers.
+    #[prop_or_default]
+    pub max_selected: Option<usize>,
+    /// Receives the search text when losing focus clears it without anything
+    /// being selected (e.g. to hint "press Enter to select", or restore it)
+    #[prop_or_default]
+    pub ondiscard: Option<Callback<String>>,
+    /// Receives the query whenever a search matches no options (e.g. to log
+    /// missing catalog entries, or fall back to a server-side search)
+    #[prop_or_default]
+    pub onnoresults: Option<Callback<String>>,
+    /// Receives the search text as it is typed or cleared (e.g. to load
+    /// matching options from a server)
+    #[prop_or_default]
+    pub onsearch: Option<Callback<String>>,
+    /// Receives the index of an option whose action button was clicked (e.g. to
+    /// preview or edit it). The button is shown on each row when this is set,
+    /// and clicking it doesn't select the option or close the dropdown.
+    #[prop_or_default]
+    pub onoptionaction: Option<Callback<usize>>,
+    /// Icon classes of the option action button (see `onoptionaction`)
+    #[prop_or_else(|| String::from("fas fa-info-circle"))]
+    pub option_action_icon: String,
+    /// Accessible label of the option action button, followed by the option's label
+    #[prop_or_else(|| String::from("Details of"))]
+    pub option_action_label: String,
+
+    /// Validation error shown under the input, which is marked `aria-invalid`
+    /// and described by the message (for assistive technology)
+    #[prop_or_default]
+    pub error: Option<String>,
+
+    /// Receives the selected indices which no longer point at an option (e.g.
+    /// after the options shrank), whenever they change (an empty list once
+    /// they were all deselected or point at options again). They are shown as
+    /// warning tags (or a warning in single mode) until deselected.
+    #[prop_or_default]
+    pub oninvalidselection: Option<Callback<Vec<usize>>>,
+    /// Label shown in place of a selection which no longer points at an option
+    #[prop_or_else(|| String::from("No longer available"))]
+    pub invalid_label: String,
+
+    /// Receives the selection whenever it changes, like the `onchange` of a
+    /// native `<select>`: `ChangeData::Value` with the value of the (first)
+    /// selected option, or an empty string if there is none. Eases moving
+    /// form code over from `<select>` elements.
+    #[prop_or_default]
+    pub onchange: Option<Callback<ChangeData>>,
+    /// Receives the values of all selected options whenever the selection
+    /// changes (like reading the `selectedOptions` of a `<select multiple>`)
+    #[prop_or_default]
+    pub onchange_values: Option<Callback<Vec<String>>>,
+    /// Value reported for each option by `onchange` and `onchange_values`,
+    /// like the `value` of an `<option>` (defaults to its label)
+    #[prop_or_default]
+    pub change_value: Option<SelectValue<T, String>>,
+
+    /// Receives a controller handle once the component is mounted
+    #[prop_or_default]
+    pub oncontroller: Option<Callback<SelectController<T>>>,
+    /// Receives performance metrics after each render
+    #[prop_or_default]
+    pub onmetrics: Option<Callback<SelectMetrics>>,
 
     #[prop_or_else(|| String::from("Type to search"))]
     pub placeholder: String,
+    /// `id` of the search input, so a `<label for=…>` can point at it
+    #[prop_or_default]
+    pub input_id: Option<String>,
+    /// `autocomplete` of the search input (off by default, so browser autofill
+    /// doesn't cover the dropdown)
+    #[prop_or_else(|| String::from("off"))]
+    pub autocomplete: String,
+    /// Let the browser fill in the search input from the user's saved details
+    /// (overriding `autocomplete`). Filled-in values are resolved against the
+    /// options (by `autofill_key`, then by label) and selected.
+    #[prop_or_default]
+    pub autofill: Option<Autofill>,
+    /// Key of each option matched (ignoring case) against autofilled values
+    /// before the labels, e.g. the ISO code for `Autofill::Country`
+    #[prop_or_default]
+    pub autofill_key: Option<SelectValue<T, String>>,
+    /// `inputmode` of the search input (e.g. `"numeric"`)
+    #[prop_or_default]
+    pub inputmode: Option<String>,
+    /// `enterkeyhint` of the search input, labelling the action key of virtual
+    /// keyboards. With `"done"`, that key commits the highlighted option and
+    /// dismisses the keyboard.
+    #[prop_or_else(|| String::from("done"))]
+    pub enterkeyhint: String,
+    /// `autocorrect` of the search input (off by default, since corrected
+    /// codes or IDs no longer match)
+    #[prop_or_else(|| String::from("off"))]
+    pub autocorrect: String,
+    /// `autocapitalize` of the search input (`"none"` by default)
+    #[prop_or_else(|| String::from("none"))]
+    pub autocapitalize: String,
+    /// `spellcheck` of the search input (off by default, `None` leaves it to
+    /// the browser)
+    #[prop_or(Some(false))]
+    pub spellcheck: Option<bool>,
+    /// `maxlength` of the search input
+    #[prop_or_default]
+    pub maxlength: Option<usize>,
+    /// Prompt shown (e.g. "Select a value…") in place of the implicit initial
+    /// selection of a `Selection::AlwaysOne` until a value is actually picked.
+    /// Check `SelectState::has_choice` before submitting.
+    #[prop_or_default]
+    pub prompt: Option<String>,
+    /// Label for an explicit row at the top of the dropdown which clears the
+    /// selection (emitting `onremoved`). Only shown for `Selection::MaybeOne`.
+    #[prop_or_default]
+    pub none_label: Option<String>,
+    /// When the dropdown opens
+    #[prop_or_default]
+    pub open_on: OpenTrigger,
+    /// Which highlight Enter commits (and which is styled as active)
+    #[prop_or_default]
+    pub enter_commits: HighlightSource,
+    /// Render a checkbox on each option (multiple mode only). Picking an option
+    /// toggles it and keeps the dropdown open.
+    #[prop_or_default]
+    pub checkboxes: bool,
+    /// Group options under headers (in order of each group's first option). In
+    /// multiple mode, picking a header selects every matching option of the
+    /// group, or deselects them if they are all selected. While searching, each
+    /// header shows its number of matches, and groups without any are hidden.
+    ///
+    /// Ignored for states built with `SelectState::from_entries`, which carry
+    /// their own headers.
+    #[prop_or_default]
+    pub group_by: Option<SelectGroup<T>>,
+    /// Show a chevron on each `group_by` header which collapses its options (or
+    /// Enter on the header, in single mode). Groups with matches are expanded
+    /// while searching. With `persist_key`, the collapsed groups are kept in
+    /// `sessionStorage`.
+    #[prop_or_default]
+    pub collapsible_groups: bool,
+    /// Draw a divider after each option for which this returns true (except
+    /// after the last row). Dividers are skipped by keyboard navigation.
+    #[prop_or_default]
+    pub divider_after: Option<SelectPredicate<T>>,
+    /// Rows at the bottom of the dropdown which run a callback instead of
+    /// selecting (e.g. "Refresh list"). They can be reached with the keyboard,
+    /// and are shown even when no options match.
+    /// Like `OptionEntry::Action`, picking one closes the dropdown and runs it.
+    #[prop_or_default]
+    pub actions: Vec<(String, SelectAction)>,
+    /// Label and callback of an action row always shown last in the dropdown
+    /// (e.g. "Manage categories…"), which can be reached with End
+    #[prop_or_default]
+    pub manage_action: Option<(String, SelectAction)>,
+    /// When no options match the search, offer up to three labels close to it
+    /// (by edit distance) as "Did you mean" rows, which search for that label
+    /// when picked
+    #[prop_or_default]
+    pub did_you_mean: bool,
+    /// Select the option whose label equals the search text (ignoring case)
+    /// when the input loses focus, e.g. after pasting a known value. Such an
+    /// option is always highlighted while typing.
+    #[prop_or_default]
+    pub select_exact_on_blur: bool,
+    /// Select the highlighted option when the input loses focus (e.g. tabbing
+    /// out) while a query is typed, instead of discarding the typing
+    #[prop_or_default]
+    pub commit_highlight_on_blur: bool,
+    /// List the selected options first (above a divider), so picks in a long
+    /// multiple select can be reviewed without scrolling. With `group_by` they
+    /// come first within each group; ignored for `SelectState::from_entries`.
+    #[prop_or_default]
+    pub selected_first: bool,
+    /// Order of the options matching a search (e.g. exact matches first, then
+    /// alphabetical), independent of the order of the options themselves
+    #[prop_or_default]
+    pub sort_filtered: Option<SelectOrder<T>>,
+    /// Only render the first N matching options, followed by a "Show N more…"
+    /// row which reveals the rest (a lighter alternative to virtualization for
+    /// mid-sized lists)
+    #[prop_or_default]
+    pub max_results: Option<usize>,
+    /// List every option while the search box is empty (otherwise the menu
+    /// only shows `start_typing_label` until something is typed, e.g. for
+    /// remote-backed options)
+    #[prop_or(true)]
+    pub show_all_on_focus: bool,
+    /// Content of the dropdown when no options are shown, given whether there
+    /// are no options at all or the search matched none (defaults to an icon
+    /// with "No Data" or "No matches for …")
+    #[prop_or_default]
+    pub empty_view: Option<SelectEmptyView>,
+    /// Hint shown in the menu before a query is typed (without `show_all_on_focus`)
+    #[prop_or_else(|| String::from("Start typing to search"))]
+    pub start_typing_label: String,
+    /// Remember recent search queries (those an option was picked from), and
+    /// show them as chips while the search box is focused and empty
+    #[prop_or_default]
+    pub search_history: bool,
+    /// Key under which the search history is kept in `localStorage`, so it
+    /// survives reloads. If not set, it only lasts as long as the component.
+    #[prop_or_default]
+    pub persist_key: Option<String>,
+
+    /// Delimiter used when copying the selected items (Ctrl+C in multiple mode)
+    #[prop_or_else(|| String::from(", "))]
+    pub copy_delimiter: String,
+    /// Format used for each item when copying the selection (defaults to `display`)
+    #[prop_or_default]
+    pub copy_format: Option<SelectDisplay<T>>,
+
+    /// Characters separating values pasted into the search box (multiple mode),
+    /// except inside double quotes. Pastes without a delimiter are treated as
+    /// normal search text.
+    #[prop_or_else(|| vec![',', '\n'])]
+    pub paste_delimiters: Vec<char>,
+    /// How pasted values are matched to options
+    #[prop_or_default]
+    pub paste_match: PasteMatch,
+    /// Receives the pasted values that didn't match any option
+    #[prop_or_default]
+    pub onpastefailed: Option<Callback<Vec<String>>>,
+    /// Show a "paste list" button (multiple mode) opening a dialog where many
+    /// values can be pasted, previewed and selected at once
+    #[prop_or_default]
+    pub bulk_entry: bool,
     #[prop_or_default]
     pub readonly: bool,
     #[prop_or_default]
@@ -61,11 +528,85 @@ impl<T> Clone for SelectProps<T> {
 
             state: self.state.clone(),
             display: self.display.clone(),
+            tooltip: self.tooltip.clone(),
+            avatar: self.avatar.clone(),
+            badge: self.badge.clone(),
+            preview: self.preview.clone(),
+            theme: self.theme.clone(),
+            class: self.class.clone(),
+            menu_class: self.menu_class.clone(),
+            scroll_parent: self.scroll_parent.clone(),
+            menu_z_index: self.menu_z_index,
+            menu_enter_class: self.menu_enter_class.clone(),
+            menu_leave_class: self.menu_leave_class.clone(),
+            menu_leave_ms: self.menu_leave_ms,
+            disable_animations: self.disable_animations,
+            item_class: self.item_class.clone(),
+            tag_class_extra: self.tag_class_extra.clone(),
+            active_item_class: self.active_item_class.clone(),
+            selected_item_class: self.selected_item_class.clone(),
+            test_id: self.test_id.clone(),
+            debug: self.debug,
 
             onselected: self.onselected.clone(),
             onremoved: self.onremoved.clone(),
+            onselectedmany: self.onselectedmany.clone(),
+            onremovedmany: self.onremovedmany.clone(),
+            footer: self.footer.clone(),
+            max_selected: self.max_selected,
+            ondiscard: self.ondiscard.clone(),
+            onnoresults: self.onnoresults.clone(),
+            onsearch: self.onsearch.clone(),
+            onoptionaction: self.onoptionaction.clone(),
+            option_action_icon: self.option_action_icon.clone(),
+            option_action_label: self.option_action_label.clone(),
+            error: self.error.clone(),
+            oninvalidselection: self.oninvalidselection.clone(),
+            invalid_label: self.invalid_label.clone(),
+            onchange: self.onchange.clone(),
+            onchange_values: self.onchange_values.clone(),
+            change_value: self.change_value.clone(),
+            oncontroller: self.oncontroller.clone(),
+            onmetrics: self.onmetrics.clone(),
 
             placeholder: self.placeholder.clone(),
+            input_id: self.input_id.clone(),
+            autocomplete: self.autocomplete.clone(),
+            autofill: self.autofill,
+            autofill_key: self.autofill_key.clone(),
+            inputmode: self.inputmode.clone(),
+            enterkeyhint: self.enterkeyhint.clone(),
+            autocorrect: self.autocorrect.clone(),
+            autocapitalize: self.autocapitalize.clone(),
+            spellcheck: self.spellcheck,
+            maxlength: self.maxlength,
+            prompt: self.prompt.clone(),
+            none_label: self.none_label.clone(),
+            open_on: self.open_on,
+            enter_commits: self.enter_commits,
+            checkboxes: self.checkboxes,
+            group_by: self.group_by.clone(),
+            collapsible_groups: self.collapsible_groups,
+            divider_after: self.divider_after.clone(),
+            actions: self.actions.clone(),
+            manage_action: self.manage_action.clone(),
+            did_you_mean: self.did_you_mean,
+            select_exact_on_blur: self.select_exact_on_blur,
+            commit_highlight_on_blur: self.commit_highlight_on_blur,
+            selected_first: self.selected_first,
+            sort_filtered: self.sort_filtered.clone(),
+            max_results: self.max_results,
+            show_all_on_focus: self.show_all_on_focus,
+            empty_view: self.empty_view.clone(),
+            start_typing_label: self.start_typing_label.clone(),
+            search_history: self.search_history,
+            persist_key: self.persist_key.clone(),
+            copy_delimiter: self.copy_delimiter.clone(),
+            copy_format: self.copy_format.clone(),
+            paste_delimiters: self.paste_delimiters.clone(),
+            paste_match: self.paste_match,
+            onpastefailed: self.onpastefailed.clone(),
+            bulk_entry: self.bulk_entry,
             readonly: self.readonly,
             disabled: self.disabled,
             loading: self.loading,
@@ -78,26 +619,249 @@ impl<T> PartialEq for SelectProps<T> {
         self.readonly == other.readonly && self.disabled == other.disabled && self.loading == other.loading &&
             self.state == other.state
             // && Arc::ptr_eq(&self.filter, &other.filter) // TODO: don't ignore filter changes?
+            && self.display == other.display
+            && self.tooltip == other.tooltip
+            && self.avatar == other.avatar
+            && self.badge == other.badge
+            && self.preview == other.preview
             && self.omit_selected == other.omit_selected
+            && self.theme == other.theme
+            && self.class == other.class
+            && self.menu_class == other.menu_class
+            && self.scroll_parent == other.scroll_parent
+            && self.menu_z_index == other.menu_z_index
+            && self.menu_enter_class == other.menu_enter_class
+            && self.menu_leave_class == other.menu_leave_class
+            && self.menu_leave_ms == other.menu_leave_ms
+            && self.disable_animations == other.disable_animations
+            && self.item_class == other.item_class
+            && self.tag_class_extra == other.tag_class_extra
+            && self.active_item_class == other.active_item_class
+            && self.selected_item_class == other.selected_item_class
+            && self.test_id == other.test_id
+            && self.debug == other.debug
             && self.placeholder == other.placeholder
+            && self.input_id == other.input_id
+            && self.autocomplete == other.autocomplete
+            && self.autofill == other.autofill
+            && self.autofill_key == other.autofill_key
+            && self.inputmode == other.inputmode
+            && self.enterkeyhint == other.enterkeyhint
+            && self.autocorrect == other.autocorrect
+            && self.autocapitalize == other.autocapitalize
+            && self.spellcheck == other.spellcheck
+            && self.maxlength == other.maxlength
+            && self.prompt == other.prompt
+            && self.none_label == other.none_label
+            && self.open_on == other.open_on
+            && self.enter_commits == other.enter_commits
+            && self.checkboxes == other.checkboxes
+            && self.group_by == other.group_by
+            && self.collapsible_groups == other.collapsible_groups
+            && self.divider_after == other.divider_after
+            && self.actions == other.actions
+            && self.manage_action == other.manage_action
+            && self.did_you_mean == other.did_you_mean
+            && self.select_exact_on_blur == other.select_exact_on_blur
+            && self.commit_highlight_on_blur == other.commit_highlight_on_blur
+            && self.selected_first == other.selected_first
+            && self.sort_filtered == other.sort_filtered
+            && self.max_results == other.max_results
+            && self.show_all_on_focus == other.show_all_on_focus
+            && self.empty_view == other.empty_view
+            && self.start_typing_label == other.start_typing_label
+            && self.search_history == other.search_history
+            && self.persist_key == other.persist_key
+            && self.copy_delimiter == other.copy_delimiter
+            && self.copy_format == other.copy_format
+            && self.paste_delimiters == other.paste_delimiters
+            && self.paste_match == other.paste_match
+            && self.onpastefailed == other.onpastefailed
+            && self.bulk_entry == other.bulk_entry
             && self.onselected == other.onselected
             && self.onremoved == other.onremoved
+            && self.onselectedmany == other.onselectedmany
+            && self.onremovedmany == other.onremovedmany
+            && self.footer == other.footer
+            && self.max_selected == other.max_selected
+            && self.ondiscard == other.ondiscard
+            && self.onnoresults == other.onnoresults
+            && self.onsearch == other.onsearch
+            && self.onoptionaction == other.onoptionaction
+            && self.option_action_icon == other.option_action_icon
+            && self.option_action_label == other.option_action_label
+            && self.error == other.error
+            && self.oninvalidselection == other.oninvalidselection
+            && self.invalid_label == other.invalid_label
+            && self.onchange == other.onchange
+            && self.onchange_values == other.onchange_values
+            && self.change_value == other.change_value
+            && self.oncontroller == other.oncontroller
+            && self.onmetrics == other.onmetrics
+    }
+}
+
+/// What opens the dropdown (besides `SelectController::open`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenTrigger {
+    /// Focusing the input (or clicking, typing or pressing an arrow key)
+    Focus,
+    /// Clicking the input (or typing or pressing an arrow key)
+    Click,
+    /// Typing a query (or pressing an arrow key)
+    Input,
+    /// Only `SelectController::open`
+    Manual,
+}
+
+impl Default for OpenTrigger {
+    fn default() -> Self {
+        OpenTrigger::Focus
+    }
+}
+
+/// How many of a group's (matching) options are selected
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GroupCheck {
+    Unchecked,
+    Partial,
+    Checked,
+}
+
+/// Where the label and callback of an action row come from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActionSource {
+    /// An `OptionEntry::Action` of the state (position in the layout)
+    Entry(usize),
+    /// The `actions` prop (position in it)
+    Prop(usize),
+    /// The `manage_action` prop
+    Manage,
+}
+
+/// Text of the bulk entry dialog, with its values resolved to options (only
+/// when the text or props change, not on every render)
+struct BulkEntry {
+    text: String,
+    matched: Vec<usize>,
+    unmatched: Vec<String>,
+}
+
+/// A row of the dropdown menu
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Row {
+    /// The explicit "none" row
+    None,
+    /// An option (global index, selected)
+    Item(usize, bool),
+    /// A group header (position in `groups`)
+    Group(usize),
+    /// A non-interactive divider
+    Divider,
+    /// A header from the state's entries (position in the layout)
+    Header(usize),
+    /// A row running an action instead of selecting an option
+    Action(ActionSource),
+    /// A "Did you mean" row (position in the suggestions)
+    Suggestion(usize),
+    /// Reveals the options hidden by `max_results` (how many)
+    ShowMore(usize),
+}
+
+/// Drop headers without any rows under them (e.g. when filtered out), and
+/// leading, trailing or repeated dividers
+fn tidy_rows(rows: Vec<Row>) -> Vec<Row> {
+    let mut tidy: Vec<Row> = Vec::with_capacity(rows.len());
+    for (i, &row) in rows.iter().enumerate() {
+        match row {
+            Row::Header(_) => {
+                if matches!(rows.get(i + 1), Some(Row::Item(..)) | Some(Row::Action(_))) {
+                    tidy.push(row);
+                }
+            }
+            Row::Divider => {
+                if !matches!(tidy.last(), None | Some(Row::Divider) | Some(Row::None)) {
+                    tidy.push(row);
+                }
+            }
+            _ => tidy.push(row),
+        }
     }
+    if tidy.last() == Some(&Row::Divider) {
+        tidy.pop();
+    }
+    tidy
 }
 
 pub enum Msg {
     Noop,
 
     Input(String),
+    /// Text filled in by the browser (or `SelectController::autofill`)
+    Autofill(String),
+    /// Delimited text pasted into the search box
+    Paste(String),
+
+    OpenBulk,
+    BulkInput(String),
+    ApplyBulk,
+    CloseBulk,
     ClearSearch,
-    Filtered,
+    /// Filtering completed (generation, duration in ms, matching indices)
+    Filtered(usize, f64, Vec<usize>),
+    /// The state was changed (possibly by another component sharing it)
+    StateChanged,
 
     Selected(usize),
     Removed(usize),
+    ClearSelection,
+    /// Select or remove an option without closing the dropdown
+    Toggle(usize),
+    /// The action button of an option was clicked (see `onoptionaction`)
+    OptionAction(usize),
+    /// Select every matching option of a group, or remove them if all are selected
+    ToggleGroup(usize),
+    /// Collapse or expand a group (with `collapsible_groups`)
+    ToggleCollapsed(usize),
+    /// Run an action row's callback, then close the dropdown
+    Action(ActionSource),
+    /// Reveal the options hidden by `max_results`
+    ShowMore,
+    /// Select every option between the range anchor and this row position
+    SelectRange(usize),
     Hover(usize),
+    Unhover,
+    /// A touch started on the row at this position
+    TouchStart(usize),
+    /// The touch moved (e.g. scrolling) or was interrupted
+    TouchCancel,
+    /// The touch ended (a long press toggles the row in multiple mode)
+    TouchEnd(TouchEvent),
 
     Focus,
+    Click,
     Blur,
+    /// The search input lost focus (ignored while interacting with the menu)
+    InputBlur(FocusEvent),
+    /// An element inside the menu (e.g. a field in the footer) lost focus
+    MenuFocusOut(FocusEvent),
+    /// The pointer was pressed on the trigger (before it focuses the input)
+    TriggerPointerDown,
+    MenuPointerDown,
+    /// The pointer pressed inside the menu was released (anywhere)
+    PointerReleased,
+    MenuPointerUp(PointerEvent),
+    /// The wheel turned over the menu (kept from scrolling the page behind it)
+    MenuWheel(WheelEvent),
+    /// Open the dropdown without focusing
+    Open,
+    /// Keyboard-highlight the row at this position
+    Highlight(usize),
+    /// The menu opened, or the page (or a container) scrolled or was resized
+    /// while it is open: check whether it should open upwards
+    Reposition,
+    /// The leave transition started with this generation is over
+    Left(usize),
     KeyPress(KeyboardEvent),
 }
 
@@ -106,26 +870,132 @@ impl<T: 'static> Component for Select<T> {
     type Message = Msg;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        Self {
-            link,
+        let (groups, group_of) = Self::compute_groups(&props);
+        let mut select = Self {
             focused: false,
+            has_focus: false,
             selection_index: 0,
-            search_text: String::new(),
+            hover_index: None,
+            keyboard: false,
+            trigger_pointer_down: false,
+            range_anchor: None,
+            input_ref: NodeRef::default(),
+            caret: None,
+            menu_pointer_down: false,
+            release_listener: None,
+            touch: None,
+            highlight_ref: NodeRef::default(),
+            scroll_to_highlight: false,
+            dropdown_ref: NodeRef::default(),
+            menu_ref: NodeRef::default(),
+            content_ref: NodeRef::default(),
+            drop_up: false,
+            leaving: false,
+            leave_generation: 0,
+            reduced_motion: motion::prefers_reduced_motion(),
+            viewport_listener: None,
+            // Start from the state's query (e.g. restored from the URL), if any
+            search_text: props.state.query().unwrap_or_default(),
+            shared_query: Rc::new(RefCell::new(props.state.query().unwrap_or_default())),
+            active_tag: None,
+            reported_invalid: Vec::new(),
+            reported_selection: Self::selected_indices(&props.state),
+            error_id: ids::unique_id("error"),
+            limit_reached: false,
+            bulk: None,
+            history: Self::load_history(&props),
+            collapsed: Self::load_collapsed(&props),
+            labels: Self::compute_labels(&props),
+            groups,
+            group_of,
+            group_checkboxes: RefCell::new(Vec::new()),
+            visible: RefCell::new(None),
+            filtered: None,
+            expanded: false,
+            suggestions: Vec::new(),
+            filter_generation: 0,
+            filter_revision: props.state.filter_revision(),
+            meta_revision: props.state.meta_revision(),
+            subscription: Self::subscribe(&link, &props.state),
+            filter_duration: None,
+            rendered_items: Cell::new(0),
+            render_count: 0,
+            link,
             props,
+        };
+        if !select.search_text.is_empty() {
+            select.refilter_now();
         }
+        select
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
         if self.props != props {
+            self.save_caret();
             if props.disabled {
                 self.focused = false;
+                self.has_focus = false;
                 self.selection_index = 0;
                 self.search_text.clear();
             }
+            let options_changed = !self.props.state.options.ptr_eq(&props.state.options);
+            if options_changed || self.props.display != props.display {
+                self.labels = Self::compute_labels(&props);
+            }
+            if options_changed
+                || self.props.group_by != props.group_by
+                || self.meta_revision != props.state.meta_revision()
+            {
+                let (groups, group_of) = Self::compute_groups(&props);
+                self.groups = groups;
+                self.group_of = group_of;
+                self.meta_revision = props.state.meta_revision();
+            }
+            if self.props.search_history != props.search_history
+                || self.props.persist_key != props.persist_key
+            {
+                self.history = Self::load_history(&props);
+            }
+            if self.props.collapsible_groups != props.collapsible_groups
+                || self.props.persist_key != props.persist_key
+            {
+                self.collapsed = Self::load_collapsed(&props);
+            }
+            if self.props.state != props.state {
+                self.props.state.unsubscribe(self.subscription);
+                self.subscription = Self::subscribe(&self.link, &props.state);
+                self.filter_revision = props.state.filter_revision();
+            }
+            if self.props.state != props.state
+                || self.props.omit_selected != props.omit_selected
+                || self.props.group_by != props.group_by
+                || self.props.divider_after != props.divider_after
+                || self.props.show_all_on_focus != props.show_all_on_focus
+            {
+                self.visible.replace(None);
+            }
+            let order_changed = self.props.sort_filtered != props.sort_filtered;
             self.props = props;
+            // The options, labels or matching may have changed under the dialog
+            if let Some(bulk) = self.bulk.take() {
+                self.bulk = Some(self.resolve_bulk(bulk.text));
+            }
+            if options_changed {
+                // The old matches refer to the old options, so don't wait for a future
+                self.refilter_now();
+                self.hover_index = None;
+                self.range_anchor = None;
+                self.clamp_highlight();
+            } else if self.props.disabled || order_changed {
+                self.refilter();
+            } else {
+                // The labels or `did_you_mean` may have changed
+                self.update_suggestions();
+            }
             true
         } else {
-            false
+            // The state may have been mutated through a shared handle
+            !self.is_visible_current()
         }
     }
 
@@ -133,39 +1003,136 @@ impl<T: 'static> Component for Select<T> {
         match msg {
             Msg::Noop => false,
 
-            Msg::Filtered => true,
+            Msg::Filtered(generation, duration, indices) => {
+                self.filter_duration = Some(duration);
+                if generation != self.filter_generation {
+                    log::debug!(
+                        "stale filter result (generation {}, current {})",
+                        generation,
+                        self.filter_generation
+                    );
+                    return false;
+                }
+
+                let matched = indices.len();
+                self.filtered = Some(Rc::new(indices));
+                self.visible.replace(None);
+                log::debug!(
+                    "filtered {:?}: {} of {} options ({} shown) in {:.2}ms",
+                    self.search_text,
+                    matched,
+                    self.props.state.options.len(),
+                    self.visible_items().len(),
+                    duration
+                );
+                self.update_suggestions();
+                self.report_no_results();
+                self.clamp_highlight();
+                if let Some(position) = self.exact_match_position() {
+                    self.selection_index = position;
+                    self.hover_index = None;
+                    self.scroll_to_highlight = true;
+                }
+                true
+            }
+
+            Msg::StateChanged => {
+                let revision = self.props.state.filter_revision();
+                if revision != self.filter_revision {
+                    self.filter_revision = revision;
+                    self.refilter();
+                }
+                let meta_revision = self.props.state.meta_revision();
+                if meta_revision != self.meta_revision {
+                    self.meta_revision = meta_revision;
+                    let (groups, group_of) = Self::compute_groups(&self.props);
+                    self.groups = groups;
+                    self.group_of = group_of;
+                }
+                !self.is_visible_current()
+            }
 
             Msg::Input(input) => {
                 if self.props.disabled || self.props.readonly {
                     return false;
                 }
 
-                self.focused = true;
-                self.search_text = input.clone();
+                if self.props.open_on != OpenTrigger::Manual {
+                    self.focused = true;
+                }
+                self.search_text = input;
+                self.save_caret();
+                self.limit_reached = false;
+                self.emit_search();
+                self.refilter();
+                true
+            }
 
-                let state = self.props.state.clone();
-                self.link.send_future(async move {
-                    if input.is_empty() {
-                        state.unfilter().await;
-                    } else {
-                        state.filter(&input).await;
+            Msg::Autofill(text) => {
+                if self.props.disabled || self.props.readonly {
+                    return false;
+                }
+                match self.resolve_autofill(&text) {
+                    // Already picked, so just show it instead of the filled-in text
+                    Some(index) if self.props.state.is_selected(index) => {
+                        self.update(Msg::ClearSearch)
                     }
-                    Msg::Filtered
-                });
+                    Some(index) => self.update(Msg::Selected(index)),
+                    // Leave unknown values as search text, to be picked from
+                    None => self.update(Msg::Input(text)),
+                }
+            }
+
+            Msg::Paste(text) => {
+                let tokens = paste::split_tokens(&text, &self.props.paste_delimiters);
+                let (matched, unmatched) = self.resolve_tokens(tokens);
+                if !matched.is_empty() {
+                    self.emit_selected_many(matched);
+                }
+                if let (false, Some(ref onpastefailed)) =
+                    (unmatched.is_empty(), &self.props.onpastefailed)
+                {
+                    onpastefailed.emit(unmatched);
+                }
+                // Show the notice if `max_selected` cut the paste short
+                self.limit_reached
+            }
+
+            Msg::OpenBulk => {
+                self.bulk = Some(self.resolve_bulk(String::new()));
+                true
+            }
+
+            Msg::BulkInput(text) => {
+                self.bulk = Some(self.resolve_bulk(text));
+                true
+            }
+
+            Msg::ApplyBulk => {
+                if let Some(bulk) = self.bulk.take() {
+                    self.link.send_message(Msg::Paste(bulk.text));
+                }
+                true
+            }
+
+            Msg::CloseBulk => {
+                self.bulk = None;
                 true
             }
 
             Msg::ClearSearch => {
-                let options = self.props.state.clone();
-                self.link.send_future(async move {
-                    options.unfilter().await;
-                    Msg::Filtered
-                });
                 self.search_text.clear();
+                self.emit_search();
+                self.refilter();
                 true
             }
 
             Msg::Selected(idx) => {
+                if !self.props.state.is_selected(idx) && self.limit_picks(vec![idx]).is_empty() {
+                    // Keep the menu open to show the notice
+                    return true;
+                }
+                self.remember_query();
                 if let Some(ref onselected) = self.props.onselected {
                     onselected.emit(idx);
                 }
@@ -175,162 +1142,2152 @@ impl<T: 'static> Component for Select<T> {
             }
 
             Msg::Removed(idx) => {
+                self.limit_reached = false;
                 if let Some(ref onremoved) = self.props.onremoved {
                     onremoved.emit(idx);
                 }
                 false
             }
 
-            Msg::Hover(idx) => {
-                self.selection_index = idx;
+            Msg::ClearSelection => {
+                if let (Some(ref onremoved), Some((idx, _))) =
+                    (&self.props.onremoved, self.props.state.first_selected())
+                {
+                    onremoved.emit(idx);
+                }
+                self.link
+                    .send_message_batch(vec![Msg::ClearSearch, Msg::Blur]);
+                false
+            }
+
+            Msg::Toggle(idx) => {
+                if self.props.state.is_selected(idx) {
+                    self.link.send_message(Msg::Removed(idx));
+                } else if self.limit_picks(vec![idx]).is_empty() {
+                    return true;
+                } else {
+                    self.remember_query();
+                    if let Some(ref onselected) = self.props.onselected {
+                        onselected.emit(idx);
+                    }
+                }
+                false
+            }
+
+            Msg::OptionAction(idx) => {
+                if let Some(ref onoptionaction) = self.props.onoptionaction {
+                    onoptionaction.emit(idx);
+                }
+                false
+            }
+
+            Msg::ToggleCollapsed(group) => {
+                self.toggle_collapsed(group);
+                self.clamp_highlight();
                 true
             }
 
-            Msg::Focus => {
-                if self.props.disabled || self.props.readonly {
+            Msg::ToggleGroup(group) => {
+                if !self.props.state.is_multiple() {
                     return false;
                 }
-                self.focused = true;
+                let members = self.group_members(group);
+                if self.group_check(group) == GroupCheck::Checked {
+                    self.emit_removed_many(members.into_iter().map(|(index, _)| index).collect());
+                } else {
+                    self.emit_selected_many(
+                        members
+                            .into_iter()
+                            .filter(|&(_, selected)| !selected)
+                            .map(|(index, _)| index)
+                            .collect(),
+                    );
+                }
+                self.limit_reached
+            }
+
+            Msg::Action(source) => {
+                if let Some((_, callback)) = self.action(source) {
+                    callback.call();
+                }
+                self.link
+                    .send_message_batch(vec![Msg::ClearSearch, Msg::Blur]);
+                false
+            }
+
+            Msg::ShowMore => {
+                self.expanded = true;
                 true
             }
 
-            Msg::Blur => {
-                self.focused = false;
-                self.selection_index = 0;
-                self.search_text.clear();
+            Msg::SelectRange(position) => {
+                self.select_range(position);
                 true
             }
 
-            Msg::KeyPress(event) => {
-                if self.props.disabled || self.props.readonly {
+            Msg::Hover(idx) => {
+                let changed = self.hover_index != Some(idx);
+                self.navigate(crate::core::Input::Hover(idx));
+                let left_keyboard = std::mem::take(&mut self.keyboard);
+                // The preview follows the pointer
+                self.props.enter_commits == HighlightSource::Pointer
+                    || (changed && self.props.preview.is_some())
+                    || left_keyboard
+            }
+
+            Msg::Unhover => {
+                self.navigate(crate::core::Input::Unhover);
+                self.props.enter_commits == HighlightSource::Pointer || self.props.preview.is_some()
+            }
+
+            Msg::TouchStart(position) => {
+                if !self.props.state.is_multiple() {
                     return false;
                 }
-                match event.code().as_ref() {
-                    "Enter" => {
-                        if let Some((index, _)) =
-                            self.props.state.get_filtered(self.selection_index)
-                        {
-                            self.link.send_message(Msg::Selected(index));
-                        }
-                        false
-                    }
+                self.touch = Some((position, log::now()));
+                true
+            }
 
-                    "Escape" => {
-                        self.focused = false;
-                        self.selection_index = 0;
-                        self.search_text.clear();
-                        true
-                    }
+            Msg::TouchCancel => self.touch.take().is_some(),
 
-                    "ArrowUp" => {
-                        self.focused = true;
+            Msg::TouchEnd(event) => match self.touch.take() {
+                Some((position, start)) => {
+                    if log::now() - start >= LONG_PRESS_MS {
+                        // Suppress the emulated mouse events, which would select and close
                         let event: &Event = &event;
                         event.prevent_default();
-                        self.selection_index = self.selection_index.saturating_sub(1);
-                        true
+                        if let Some(&Row::Item(index, _)) = self.rows().get(position) {
+                            self.link.send_message(Msg::Toggle(index));
+                        }
                     }
+                    true
+                }
+                None => false,
+            },
 
-                    "ArrowDown" => {
-                        self.focused = true;
-                        let event: &Event = &event;
-                        event.prevent_default();
-                        self.selection_index += 1;
+            Msg::Focus => {
+                if self.props.disabled || self.props.readonly {
+                    return false;
+                }
+                self.has_focus = true;
+                // Focus without a press on the trigger came from the keyboard (e.g. Tab)
+                self.keyboard = !std::mem::take(&mut self.trigger_pointer_down);
+                if !self.focused && self.props.open_on == OpenTrigger::Focus {
+                    self.open();
+                }
+                true
+            }
+
+            Msg::Click => {
+                if self.props.disabled || self.props.readonly || self.focused {
+                    return false;
+                }
+                match self.props.open_on {
+                    OpenTrigger::Focus | OpenTrigger::Click => {
+                        self.open();
                         true
                     }
-
-                    _ => false,
+                    OpenTrigger::Input | OpenTrigger::Manual => false,
                 }
             }
-        }
-    }
 
-    fn view(&self) -> Html {
-        let options = if self.props.omit_selected {
-            self.props
-                .state
-                .filtered_items()
-                .into_iter()
-                .filter(|(_, selected, _)| !selected)
-                .collect::<Vec<_>>()
-        } else {
-            self.props.state.filtered_items()
-        };
+            Msg::Open => {
+                if self.props.disabled || self.props.readonly || self.focused {
+                    return false;
+                }
+                self.open();
+                true
+            }
 
-        let options = if options.is_empty() {
-            html! {
-                <div class="has-text-centered">
-                    <p>
-                        <span class="icon">
-                            <i class="fas fa-inbox" />
-                        </span>
-                    </p>
-                    <p>{"No Data"}</p>
-                </div>
+            Msg::Highlight(position) => {
+                self.selection_index = position;
+                self.hover_index = None;
+                self.scroll_to_highlight = true;
+                true
             }
-        } else {
-            options
-                .into_iter()
-                .enumerate()
-                .map(|(i, (idx, selected, item))| {
-                    html! {
-                        <a
-                            class=classes!(
-                                "dropdown-item",
-                                if self.selection_index == i {"is-active"}
-                                else if selected {"has-background-primary-light"}
-                                else {""}
-                            )
-                        >
-                            <p
-                                onmouseenter=self.link.callback(move |_| Msg::Hover(i))
-                                onmousedown=self.link.callback(move |event: MouseEvent| {
-                                    let event: &Event = &event;
-                                    event.prevent_default();
-                                    Msg::Selected(idx)
-                                })
-                            >
-                                { self.props.display.call(&item) }
-                            </p>
-                        </a>
-                    }
-                })
-                .collect::<Html>()
-        };
+
+            Msg::Reposition => match self.measure_drop_up() {
+                Some(drop_up) if drop_up != self.drop_up => {
+                    self.drop_up = drop_up;
+                    true
+                }
+                _ => false,
+            },
+
+            Msg::Left(generation) => {
+                if generation == self.leave_generation && self.leaving {
+                    self.leaving = false;
+                    // Only the closed menu is affected
+                    !self.focused
+                } else {
+                    false
+                }
+            }
+
+            Msg::InputBlur(event) => {
+                if self.menu_pointer_down || self.contains(event.related_target()) {
+                    // Keep the menu open so embedded content can be clicked or
+                    // focused (e.g. a field in the footer)
+                    false
+                } else {
+                    self.update(Msg::Blur)
+                }
+            }
+
+            Msg::MenuFocusOut(event) => {
+                // Focus left a field inside the menu for somewhere outside the select
+                if self.focused && !self.menu_pointer_down && !self.contains(event.related_target())
+                {
+                    self.update(Msg::Blur)
+                } else {
+                    false
+                }
+            }
+
+            Msg::TriggerPointerDown => {
+                self.trigger_pointer_down = true;
+                std::mem::take(&mut self.keyboard)
+            }
+
+            Msg::MenuPointerDown => {
+                self.menu_pointer_down = true;
+                let link = self.link.clone();
+                self.release_listener = listener::WindowListener::new(
+                    &[("pointerup", false), ("pointercancel", false)],
+                    move || link.send_message(Msg::PointerReleased),
+                );
+                std::mem::take(&mut self.keyboard)
+            }
+
+            Msg::PointerReleased => {
+                self.release_listener = None;
+                // Still set if the pointer was released outside of the menu (after
+                // `MenuPointerUp` would have cleared it), e.g. dragged off a row
+                if std::mem::take(&mut self.menu_pointer_down) && !self.has_focus_within() {
+                    self.update(Msg::Blur)
+                } else {
+                    false
+                }
+            }
+
+            Msg::MenuPointerUp(event) => {
+                self.menu_pointer_down = false;
+                // Return focus to the search input, unless the user clicked into a form field
+                let editable = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<Element>().ok())
+                    .map(|element| {
+                        matches!(
+                            element.tag_name().to_lowercase().as_str(),
+                            "input" | "textarea" | "select"
+                        )
+                    })
+                    .unwrap_or(false);
+                if self.focused && !editable {
+                    if let Some(input) = self.input_ref.cast::<HtmlElement>() {
+                        let _ = input.focus();
+                    }
+                }
+                false
+            }
+
+            Msg::MenuWheel(event) => {
+                // Let the menu scroll, but not past its ends (where the page would take over)
+                let contained = event
+                    .current_target()
+                    .and_then(|target| target.dyn_into::<Element>().ok())
+                    .map_or(false, |menu| {
+                        let delta = event.delta_y();
+                        let top = menu.scroll_top();
+                        let bottom = menu.scroll_height() - menu.client_height();
+                        (delta < 0.0 && top <= 0) || (delta > 0.0 && top >= bottom)
+                    });
+                if contained {
+                    let event: &Event = &event;
+                    event.prevent_default();
+                }
+                false
+            }
+
+            Msg::Blur => {
+                self.limit_reached = false;
+                if let Some(index) = self
+                    .blur_commit()
+                    .filter(|_| self.remaining_picks() != Some(0))
+                {
+                    self.remember_query();
+                    if let Some(ref onselected) = self.props.onselected {
+                        onselected.emit(index);
+                    }
+                } else if let (false, Some(ref ondiscard)) =
+                    (self.search_text.is_empty(), &self.props.ondiscard)
+                {
+                    ondiscard.emit(self.search_text.clone());
+                }
+                self.close();
+                self.has_focus = false;
+                self.selection_index = 0;
+                self.hover_index = None;
+                self.range_anchor = None;
+                self.active_tag = None;
+                self.search_text.clear();
+                self.refilter();
+                true
+            }
+
+            Msg::KeyPress(event) => {
+                if self.props.disabled || self.props.readonly {
+                    return false;
+                }
+
+                // Virtual keyboards report their action key without a `code`
+                let virtual_enter = event.code().is_empty() && event.key() == "Enter";
+                let code = if virtual_enter {
+                    String::from("Enter")
+                } else {
+                    event.code()
+                };
+                let entered_keyboard = !std::mem::replace(&mut self.keyboard, true);
+                // Any other key moves back from the tags to the search input
+                let left_tags = !matches!(
+                    code.as_str(),
+                    "ArrowLeft" | "ArrowRight" | "Backspace" | "Delete"
+                ) && self.active_tag.take().is_some();
+
+                let render = match code.as_str() {
+                    "Enter" | "NumpadEnter" => {
+                        self.commit_highlighted();
+                        if virtual_enter && self.props.enterkeyhint == "done" {
+                            // Dismiss the virtual keyboard. The query was just used, so
+                            // don't let the blur treat it as discarded.
+                            self.remember_query();
+                            self.search_text.clear();
+                            if let Some(input) = self.input_ref.cast::<HtmlElement>() {
+                                let _ = input.blur();
+                            }
+                        }
+                        false
+                    }
+
+                    // Space only acts when not typing a query (otherwise it's part of the search)
+                    "Space" if self.search_text.is_empty() && self.focused => {
+                        let event: &Event = &event;
+                        if self.is_checkbox_mode() {
+                            event.prevent_default();
+                            match self.rows().get(self.highlighted()) {
+                                Some(&Row::Item(index, _)) => {
+                                    self.link.send_message(Msg::Toggle(index))
+                                }
+                                Some(&Row::Group(group)) => {
+                                    self.link.send_message(Msg::ToggleGroup(group))
+                                }
+                                _ => {}
+                            }
+                        } else if !self.props.state.is_multiple() {
+                            event.prevent_default();
+                            self.commit_highlighted();
+                        }
+                        false
+                    }
+
+                    // Copy the selection when there's no search text that could be copied instead
+                    "KeyC"
+                        if (event.ctrl_key() || event.meta_key())
+                            && self.search_text.is_empty()
+                            && self.props.state.is_multiple() =>
+                    {
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        clipboard::write_text(&self.selection_text());
+                        false
+                    }
+
+                    "Escape" => {
+                        self.close();
+                        self.selection_index = 0;
+                        self.hover_index = None;
+                        self.range_anchor = None;
+                        self.search_text.clear();
+                        self.refilter();
+                        true
+                    }
+
+                    "ArrowUp" => {
+                        let shift = event.shift_key();
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        if self.focused && shift && self.props.state.is_multiple() {
+                            self.select_range(self.step(self.highlighted(), false));
+                        } else if self.focused {
+                            self.range_anchor = None;
+                            self.navigate(crate::core::Input::Key(crate::core::Key::ArrowUp));
+                        } else if self.props.open_on != OpenTrigger::Manual {
+                            self.open();
+                        }
+                        self.hover_index = None;
+                        self.scroll_to_highlight = true;
+                        true
+                    }
+
+                    "ArrowDown" => {
+                        let shift = event.shift_key();
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        if self.focused && shift && self.props.state.is_multiple() {
+                            self.select_range(self.step(self.highlighted(), true));
+                        } else if self.focused {
+                            self.range_anchor = None;
+                            self.navigate(crate::core::Input::Key(crate::core::Key::ArrowDown));
+                        } else if self.props.open_on != OpenTrigger::Manual {
+                            self.open();
+                        }
+                        self.hover_index = None;
+                        self.scroll_to_highlight = true;
+                        true
+                    }
+
+                    // While open, jump to the first or last row (e.g. the `manage_action`)
+                    "Home" | "End" if self.focused => {
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        self.range_anchor = None;
+                        self.navigate(crate::core::Input::Key(if code == "Home" {
+                            crate::core::Key::Home
+                        } else {
+                            crate::core::Key::End
+                        }));
+                        self.scroll_to_highlight = true;
+                        true
+                    }
+
+                    "ArrowLeft" if !self.tag_indices().is_empty() && self.caret_at_start() => {
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        let last = self.tag_indices().len() - 1;
+                        self.active_tag = Some(match self.active_tag {
+                            Some(position) => position.saturating_sub(1).min(last),
+                            None => last,
+                        });
+                        true
+                    }
+
+                    "ArrowRight" if self.active_tag.is_some() => {
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        let count = self.tag_indices().len();
+                        self.active_tag = self
+                            .active_tag
+                            .map(|position| position + 1)
+                            .filter(|&position| position < count);
+                        true
+                    }
+
+                    "Backspace" | "Delete" if self.active_tag.is_some() => {
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        let tags = self.tag_indices();
+                        let position = self.active_tag.unwrap_or_default();
+                        if let Some(&index) = tags.get(position) {
+                            self.link.send_message(Msg::Removed(index));
+                        }
+                        // Backspace moves on to the previous tag, Delete to the next
+                        let remaining = tags.len().saturating_sub(1);
+                        self.active_tag = if remaining == 0 {
+                            None
+                        } else if code == "Backspace" {
+                            Some(position.saturating_sub(1))
+                        } else {
+                            Some(position.min(remaining - 1))
+                        };
+                        true
+                    }
+
+                    // Backspace in an empty search focuses the last tag (pressing it again removes it)
+                    "Backspace"
+                        if self.search_text.is_empty() && !self.tag_indices().is_empty() =>
+                    {
+                        self.active_tag = Some(self.tag_indices().len() - 1);
+                        true
+                    }
+
+                    _ => false,
+                };
+                render || left_tags || entered_keyboard
+            }
+        }
+    }
+
+    fn view(&self) -> Html {
+        let theme = &self.props.theme;
+        let rows = self.rows();
+        let checkboxes = self.is_checkbox_mode();
+        let multiple = self.props.state.is_multiple();
+
+        self.rendered_items.set(
+            rows.iter()
+                .filter(|row| matches!(row, Row::Item(..)))
+                .count(),
+        );
+        self.group_checkboxes.borrow_mut().clear();
+
+        let empty = if rows.iter().any(|row| matches!(row, Row::Item(..))) {
+            html! {}
+        } else if self.is_awaiting_query() {
+            html! {
+                <div class="has-text-centered has-text-grey" data-testid=self.test_id("start-typing")>
+                    <p>{ &self.props.start_typing_label }</p>
+                </div>
+            }
+        } else {
+            let empty_state = self.empty_state();
+            match self.props.empty_view {
+                Some(ref empty_view) => empty_view.call(&empty_state),
+                None => html! {
+                    <div class="has-text-centered">
+                        <p>
+                            <span class="icon">
+                                <i class="fas fa-inbox" />
+                            </span>
+                        </p>
+                        <p>
+                        {
+                            match empty_state {
+                                EmptyState::Empty => String::from("No Data"),
+                                EmptyState::NoMatches { query } => format!("No matches for \"{}\"", query),
+                            }
+                        }
+                        </p>
+                    </div>
+                },
+            }
+        };
+        let preview = self.view_preview(&rows);
+        let group_counts = self.group_match_counts();
+        let options = rows
+            .into_iter()
+                .enumerate()
+                .map(|(i, row)| match row {
+                    Row::None => html! {
+                        <a
+                            key="none"
+                            ref=self.row_ref(i)
+                            class=classes!(
+                                theme.item.clone(),
+                                self.props.item_class.clone(),
+                                if self.highlighted() == i { Some(self.active_item_class()) } else { None }
+                            )
+                            data-testid=self.test_id("none")
+                        >
+                            <p
+                                onmouseenter=self.link.callback(move |_| Msg::Hover(i))
+                                onmousedown=self.link.callback(|event: MouseEvent| {
+                                    let event: &Event = &event;
+                                    event.prevent_default();
+                                    Msg::ClearSelection
+                                })
+                            >
+                                <em>{ self.props.none_label.clone().unwrap_or_default() }</em>
+                            </p>
+                        </a>
+                    },
+                    Row::Item(idx, selected) => html! {
+                        <a
+                            key=idx.to_string()
+                            ref=self.row_ref(i)
+                            class=classes!(
+                                theme.item.clone(),
+                                self.props.item_class.clone(),
+                                if self.highlighted() == i { Some(self.active_item_class()) }
+                                else if selected { Some(self.selected_item_class()) }
+                                else { None },
+                                if self.props.state.is_disabled(idx) { Some(theme.disabled_item.clone()) } else { None }
+                            )
+                            aria-disabled=if self.props.state.is_disabled(idx) { Some("true") } else { None }
+                            data-testid=self.test_id(&format!("item-{}", idx))
+                            title=self.tooltip(idx)
+                        >
+                            <p
+                                class=classes!(if self.touch.map(|(position, _)| position) == Some(i) { Some(theme.pressed_item.clone()) } else { None })
+                                onmouseenter=self.link.callback(move |_| Msg::Hover(i))
+                                ontouchstart=self.link.callback(move |_| Msg::TouchStart(i))
+                                ontouchmove=self.link.callback(|_| Msg::TouchCancel)
+                                ontouchcancel=self.link.callback(|_| Msg::TouchCancel)
+                                ontouchend=self.link.callback(Msg::TouchEnd)
+                                onmousedown=self.link.callback(move |event: MouseEvent| {
+                                    let shift = event.shift_key();
+                                    let event: &Event = &event;
+                                    event.prevent_default();
+                                    if shift && multiple { Msg::SelectRange(i) }
+                                    else if checkboxes { Msg::Toggle(idx) }
+                                    else { Msg::Selected(idx) }
+                                })
+                            >
+                                {
+                                    if checkboxes {
+                                        html! { <input type="checkbox" class="mr-2" tabindex="-1" checked=selected /> }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                                { self.view_avatar(idx, self.props.theme.avatar.clone()) }
+                                { self.label(idx) }
+                                { self.view_option_action(idx) }
+                                { self.view_badge(idx) }
+                            </p>
+                        </a>
+                    },
+                    Row::Group(group) if multiple => html! {
+                        <a
+                            key=format!("group-{}", group)
+                            ref=self.row_ref(i)
+                            class=classes!(
+                                theme.group.clone(),
+                                if self.highlighted() == i { Some(self.active_item_class()) } else { None }
+                            )
+                            data-testid=self.test_id(&format!("group-{}", group))
+                        >
+                            <p
+                                onmouseenter=self.link.callback(move |_| Msg::Hover(i))
+                                onmousedown=self.link.callback(move |event: MouseEvent| {
+                                    let event: &Event = &event;
+                                    event.prevent_default();
+                                    Msg::ToggleGroup(group)
+                                })
+                            >
+                                {
+                                    if checkboxes {
+                                        let check = self.group_check(group);
+                                        let checkbox_ref = NodeRef::default();
+                                        self.group_checkboxes.borrow_mut().push((checkbox_ref.clone(), check == GroupCheck::Partial));
+                                        html! {
+                                            <input
+                                                ref=checkbox_ref
+                                                type="checkbox"
+                                                class=classes!("mr-2", if check == GroupCheck::Partial { Some("is-indeterminate") } else { None })
+                                                tabindex="-1"
+                                                checked=check == GroupCheck::Checked
+                                                aria-checked=match check {
+                                                    GroupCheck::Unchecked => "false",
+                                                    GroupCheck::Partial => "mixed",
+                                                    GroupCheck::Checked => "true",
+                                                }
+                                            />
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                                { self.group_label(group) }
+                                { Self::view_group_count(&group_counts, group) }
+                                { self.view_collapse_toggle(group) }
+                            </p>
+                        </a>
+                    },
+                    Row::Header(position) => html! {
+                        <div
+                            key=format!("header-{}", position)
+                            class=theme.group.clone()
+                            data-testid=self.test_id(&format!("header-{}", position))
+                        >
+                            { self.entry_label(position) }
+                        </div>
+                    },
+                    Row::Action(source) => self.view_action(
+                        i,
+                        match source {
+                            ActionSource::Entry(position) => format!("action-{}", position),
+                            ActionSource::Prop(position) => format!("menu-action-{}", position),
+                            ActionSource::Manage => String::from("manage"),
+                        },
+                        self.action(source).map(|(label, _)| label).unwrap_or_default(),
+                        move || Msg::Action(source),
+                    ),
+                    Row::ShowMore(hidden) => self.view_action(
+                        i,
+                        String::from("show-more"),
+                        &format!("Show {} more…", hidden),
+                        || Msg::ShowMore,
+                    ),
+                    Row::Suggestion(position) => {
+                        let suggestion = self.suggestions.get(position).cloned().unwrap_or_default();
+                        self.view_action(
+                            i,
+                            format!("suggestion-{}", position),
+                            &format!("Did you mean: '{}'?", suggestion),
+                            move || Msg::Input(suggestion.clone()),
+                        )
+                    }
+                    Row::Divider => html! {
+                        <hr key=format!("divider-{}", i) class=theme.divider.clone() />
+                    },
+                    Row::Group(group) => html! {
+                        <div
+                            key=format!("group-{}", group)
+                            ref=self.row_ref(i)
+                            class=classes!(
+                                theme.group.clone(),
+                                if self.props.collapsible_groups && self.highlighted() == i { Some(self.active_item_class()) } else { None }
+                            )
+                            data-testid=self.test_id(&format!("group-{}", group))
+                        >
+                            { self.group_label(group) }
+                            { Self::view_group_count(&group_counts, group) }
+                            { self.view_collapse_toggle(group) }
+                        </div>
+                    },
+                })
+                .collect::<Html>();
 
         html! {
-            <div class=classes!("dropdown", if self.focused {"is-active"} else {""})>
-                <div class="dropdown-trigger">
+            <div
+                class=classes!(
+                    theme.dropdown.clone(),
+                    self.props.class.clone(),
+                    if self.focused || self.leaving { Some(theme.open.clone()) } else { None },
+                    if self.drop_up { Some(theme.up.clone()) } else { None }
+                )
+                ref=self.dropdown_ref.clone()
+            >
+                <div
+                    class=classes!(theme.trigger.clone(), if self.has_focus && self.keyboard { Some(theme.focus_visible.clone()) } else { None })
+                    data-testid=self.test_id("trigger")
+                    onpointerdown=self.link.callback(|_| Msg::TriggerPointerDown)
+                >
                 {
                     if self.props.state.is_multiple() {
                         self.view_multiple()
                     } else {
-                        self.view_single()
+                        self.view_single()
+                    }
+                }
+                </div>
+                <div
+                    class=classes!(
+                        theme.menu.clone(),
+                        self.props.menu_class.clone(),
+                        if !self.animated() {
+                            Classes::new()
+                        } else if self.focused {
+                            self.props.menu_enter_class.clone()
+                        } else {
+                            self.props.menu_leave_class.clone()
+                        }
+                    )
+                    data-testid=self.test_id("menu")
+                    style=self.props.menu_z_index.map(|z_index| format!("z-index: {}", z_index))
+                    ref=self.menu_ref.clone()
+                    onpointerdown=self.link.callback(|_| Msg::MenuPointerDown)
+                    onfocusout=self.link.callback(Msg::MenuFocusOut)
+                    onpointerup=self.link.callback(Msg::MenuPointerUp)
+                >
+                    <div
+                        class=theme.content.clone()
+                        style="overscroll-behavior: contain"
+                        ref=self.content_ref.clone()
+                        onmouseleave=self.link.callback(|_| Msg::Unhover)
+                        onwheel=self.link.callback(Msg::MenuWheel)
+                    >
+                        {
+                            match self.limit_notice() {
+                                Some(notice) => html! {
+                                    <div class=theme.notice.clone() data-testid=self.test_id("limit-notice")>{ notice }</div>
+                                },
+                                None => html! {},
+                            }
+                        }
+                        { self.view_history() }
+                        { empty }
+                        { options }
+                        {
+                            match self.props.footer {
+                                Some(ref footer) => html! {
+                                    <>
+                                        <hr class=theme.divider.clone() />
+                                        <div class="dropdown-item">{ footer.clone() }</div>
+                                    </>
+                                },
+                                None => html! {},
+                            }
+                        }
+                    </div>
+                    { preview }
+                </div>
+                { self.view_bulk_entry() }
+                {
+                    match self.props.error {
+                        Some(ref error) => html! {
+                            <p id=self.error_id.clone() class=theme.error.clone()>{ error }</p>
+                        },
+                        None => html! {},
+                    }
+                }
+                // Always rendered, so screen readers announce changes to its content
+                <div class="is-sr-only" aria-live="polite">
+                    { self.limit_notice().unwrap_or_default() }
+                </div>
+                { self.view_debug() }
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        let invalid = self.props.state.invalid_selections();
+        if invalid != self.reported_invalid {
+            log::debug!("dangling selections {:?}", invalid);
+            if let Some(ref oninvalidselection) = self.props.oninvalidselection {
+                oninvalidselection.emit(invalid.clone());
+            }
+            self.reported_invalid = invalid;
+        }
+
+        let selection = Self::selected_indices(&self.props.state);
+        if selection != self.reported_selection {
+            self.reported_selection = selection;
+            self.emit_change();
+        }
+
+        for (checkbox_ref, indeterminate) in self.group_checkboxes.borrow().iter() {
+            if let Some(checkbox) = checkbox_ref.cast::<HtmlInputElement>() {
+                checkbox.set_indeterminate(*indeterminate);
+            }
+        }
+
+        if *self.shared_query.borrow() != self.search_text {
+            self.shared_query.replace(self.search_text.clone());
+            self.props.state.set_search_text(&self.search_text);
+        }
+
+        if first_render {
+            if let Some(ref oncontroller) = self.props.oncontroller {
+                oncontroller.emit(SelectController {
+                    link: self.link.clone(),
+                    input_ref: self.input_ref.clone(),
+                    query: self.shared_query.clone(),
+                });
+            }
+        }
+
+        if let Some((start, end)) = self.caret.take() {
+            self.restore_caret(start, end);
+        }
+
+        if !self.focused {
+            self.viewport_listener = None;
+        } else if self.viewport_listener.is_none() {
+            let link = self.link.clone();
+            self.viewport_listener =
+                listener::WindowListener::new(placement::VIEWPORT_EVENTS, move || {
+                    link.send_message(Msg::Reposition)
+                });
+            self.link.send_message(Msg::Reposition);
+        }
+
+        if std::mem::take(&mut self.scroll_to_highlight) {
+            if let Some(element) = self.highlight_ref.cast::<Element>() {
+                match self.scroll_parent() {
+                    // `scrollIntoView` would also scroll the page behind the container
+                    Some(ref parent) => {
+                        if let Some(content) = self.content_ref.cast::<Element>() {
+                            placement::scroll_into(&content, &element);
+                        }
+                        placement::scroll_into(parent, &element);
+                    }
+                    None => {
+                        let mut options = ScrollIntoViewOptions::new();
+                        options.block(ScrollLogicalPosition::Nearest);
+                        element.scroll_into_view_with_scroll_into_view_options(&options);
+                    }
+                }
+            }
+        }
+
+        self.render_count += 1;
+        if let Some(ref onmetrics) = self.props.onmetrics {
+            onmetrics.emit(SelectMetrics {
+                filter_duration: self.filter_duration,
+                rendered_items: self.rendered_items.get(),
+                render_count: self.render_count,
+            });
+        }
+    }
+
+    fn destroy(&mut self) {
+        self.props.state.unsubscribe(self.subscription);
+    }
+}
+
+impl<T> Select<T> {
+    fn subscribe(link: &ComponentLink<Self>, state: &SelectState<T>) -> usize {
+        let link = link.clone();
+        state.subscribe(move || link.send_message(Msg::StateChanged))
+    }
+
+    /// Filter the options by the current search text. Matching runs in a future,
+    /// and results from superseded searches are dropped.
+    fn refilter(&mut self) {
+        self.filter_generation += 1;
+        self.expanded = false;
+        if self.search_text.is_empty() {
+            self.filtered = None;
+            self.visible.replace(None);
+            self.suggestions.clear();
+            return;
+        }
+
+        let generation = self.filter_generation;
+        let state = self.props.state.clone();
+        let input = self.search_text.clone();
+        let order = self.props.sort_filtered.clone();
+        self.link.send_future(async move {
+            let start = log::now();
+            let mut indices = state.matching_indices(&input);
+            if let Some(order) = order {
+                state.sort_indices(&mut indices, &order);
+            }
+            Msg::Filtered(generation, log::now() - start, indices)
+        });
+    }
+
+    /// Filter the options by the current search text immediately, cancelling
+    /// any pending filter future
+    fn refilter_now(&mut self) {
+        self.filter_generation += 1;
+        self.expanded = false;
+        self.filtered = if self.search_text.is_empty() {
+            None
+        } else {
+            let mut indices = self.props.state.matching_indices(&self.search_text);
+            if let Some(ref order) = self.props.sort_filtered {
+                self.props.state.sort_indices(&mut indices, order);
+            }
+            Some(Rc::new(indices))
+        };
+        self.visible.replace(None);
+        self.update_suggestions();
+        self.report_no_results();
+    }
+
+    /// Whether `label` equals the search text (ignoring case and surrounding spaces)
+    fn is_exact_match(&self, label: &str) -> bool {
+        let query = self.search_text.trim();
+        !query.is_empty() && label.trim().to_lowercase() == query.to_lowercase()
+    }
+
+    /// Position of the row whose label equals the search text, if any
+    fn exact_match_position(&self) -> Option<usize> {
+        self.rows().iter().position(
+            |row| matches!(*row, Row::Item(index, _) if self.is_exact_match(self.label(index))),
+        )
+    }
+
+    /// Unselected option whose label equals the search text, if any
+    fn exact_match(&self) -> Option<usize> {
+        let candidates = self
+            .labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| self.is_exact_match(label))
+            .map(|(index, _)| index);
+        self.props
+            .state
+            .allowed(candidates)
+            .into_iter()
+            .find(|&index| !self.props.state.is_selected(index))
+    }
+
+    /// Option to select when the input loses focus with a query typed (with
+    /// `select_exact_on_blur` or `commit_highlight_on_blur`)
+    fn blur_commit(&self) -> Option<usize> {
+        let exact = if self.props.select_exact_on_blur {
+            self.exact_match()
+        } else {
+            None
+        };
+        exact.or_else(|| {
+            if !self.props.commit_highlight_on_blur || !self.focused || self.search_text.is_empty()
+            {
+                return None;
+            }
+            match self.rows().get(self.highlighted()) {
+                Some(&Row::Item(index, false)) => Some(index),
+                _ => None,
+            }
+        })
+    }
+
+    fn emit_search(&self) {
+        if let Some(ref onsearch) = self.props.onsearch {
+            onsearch.emit(self.search_text.clone());
+        }
+    }
+
+    /// Emit `onnoresults` if the search matched no options
+    fn report_no_results(&self) {
+        if let (Some(ref indices), Some(ref onnoresults)) =
+            (&self.filtered, &self.props.onnoresults)
+        {
+            if indices.is_empty() {
+                onnoresults.emit(self.search_text.clone());
+            }
+        }
+    }
+
+    /// Search history stored under `persist_key` (if enabled)
+    fn load_history(props: &SelectProps<T>) -> Vec<String> {
+        match props.persist_key {
+            Some(ref persist_key) if props.search_history => history::load(persist_key),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Collapsed groups stored under `persist_key` (if enabled)
+    fn load_collapsed(props: &SelectProps<T>) -> Vec<String> {
+        match props.persist_key {
+            Some(ref persist_key) if props.collapsible_groups => collapsed::load(persist_key),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the options of `group` are hidden (never while searching)
+    fn is_collapsed(&self, group: usize) -> bool {
+        self.props.collapsible_groups
+            && self.search_text.is_empty()
+            && self
+                .groups
+                .get(group)
+                .map_or(false, |name| self.collapsed.contains(name))
+    }
+
+    /// Collapse or expand `group`, remembering it under `persist_key`
+    fn toggle_collapsed(&mut self, group: usize) {
+        let name = match self.groups.get(group) {
+            Some(name) => name.clone(),
+            None => return,
+        };
+        if self.collapsed.contains(&name) {
+            self.collapsed.retain(|collapsed| *collapsed != name);
+        } else {
+            self.collapsed.push(name);
+        }
+        if let Some(ref persist_key) = self.props.persist_key {
+            collapsed::store(persist_key, &self.collapsed);
+        }
+    }
+
+    /// Chevron collapsing or expanding the options of `group` (with `collapsible_groups`)
+    fn view_collapse_toggle(&self, group: usize) -> Html {
+        if !self.props.collapsible_groups {
+            return html! {};
+        }
+        let collapsed = self.is_collapsed(group);
+        html! {
+            <button
+                type="button"
+                class=self.props.theme.group_toggle.clone()
+                tabindex="-1"
+                aria-label=format!("{} {}", if collapsed { "Expand" } else { "Collapse" }, self.group_label(group))
+                aria-expanded=(!collapsed).to_string()
+                data-testid=self.test_id(&format!("group-toggle-{}", group))
+                onmousedown=self.link.callback(move |event: MouseEvent| {
+                    // Keep the header from toggling the group's selection
+                    let event: &Event = &event;
+                    event.stop_propagation();
+                    event.prevent_default();
+                    Msg::ToggleCollapsed(group)
+                })
+            >
+                <span class="icon is-small">
+                    <i class=if collapsed { "fas fa-chevron-right" } else { "fas fa-chevron-down" } />
+                </span>
+            </button>
+        }
+    }
+
+    /// Add the current search text to the history (if enabled)
+    fn remember_query(&mut self) {
+        if !self.props.search_history {
+            return;
+        }
+        history::remember(&mut self.history, &self.search_text, history::LIMIT);
+        if let Some(ref persist_key) = self.props.persist_key {
+            history::store(persist_key, &self.history);
+        }
+    }
+
+    /// Chips for the recent queries, shown while the search box is empty
+    fn view_history(&self) -> Html {
+        if !self.props.search_history || !self.search_text.is_empty() || self.history.is_empty() {
+            return html! {};
+        }
+
+        let theme = &self.props.theme;
+        html! {
+            <div class=theme.history.clone() data-testid=self.test_id("history")>
+                {
+                    self.history
+                        .iter()
+                        .map(|query| {
+                            let apply = query.clone();
+                            html! {
+                                <span
+                                    class=theme.history_chip.clone()
+                                    title=format!("Search for \"{}\"", query)
+                                    onmousedown=self.link.callback(move |event: MouseEvent| {
+                                        let event: &Event = &event;
+                                        event.prevent_default();
+                                        Msg::Input(apply.clone())
+                                    })
+                                >
+                                    { query }
+                                </span>
+                            }
+                        })
+                        .collect::<Html>()
+                }
+            </div>
+        }
+    }
+
+    /// Overlay for the `debug` prop
+    fn view_debug(&self) -> Html {
+        if !self.props.debug {
+            return html! {};
+        }
+        html! {
+            <pre class="is-size-7 mt-2" data-testid=self.test_id("debug")>
+                { format!("state: {}\n", self.props.state.debug_snapshot().to_json()) }
+                {
+                    format!(
+                        "component: open={} search={:?} highlighted={} rendered={}",
+                        self.focused,
+                        self.search_text,
+                        self.highlighted(),
+                        self.rendered_items.get()
+                    )
+                }
+            </pre>
+        }
+    }
+
+    /// Recompute the "Did you mean" suggestions for the current filter results
+    fn update_suggestions(&mut self) {
+        // Matches hidden by `omit_selected` don't count, since nothing is shown
+        self.suggestions = match self.filtered {
+            Some(_) if self.props.did_you_mean && self.visible_items().is_empty() => {
+                suggest::suggestions(&self.labels, &self.search_text, 3)
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    /// Move the keyboard highlight back onto a navigable row if the rows changed
+    /// under it (e.g. the options were replaced while the menu was open)
+    fn clamp_highlight(&mut self) {
+        self.selection_index = crate::core::clamp(&self.row_kinds(), self.selection_index);
+    }
+
+    fn compute_labels(props: &SelectProps<T>) -> Vec<String> {
+        props
+            .state
+            .iter()
+            .map(|item| props.display.call(item))
+            .collect()
+    }
+
+    fn compute_groups(props: &SelectProps<T>) -> (Vec<String>, Vec<usize>) {
+        let group_by = match props.group_by {
+            Some(ref group_by) => group_by,
+            None => return (Vec::new(), Vec::new()),
+        };
+
+        let mut groups = Vec::<String>::new();
+        let group_of = props
+            .state
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let name = props
+                    .state
+                    .meta(index)
+                    .and_then(|meta| meta.group)
+                    .unwrap_or_else(|| group_by.call(item));
+                match groups.iter().position(|group| *group == name) {
+                    Some(group) => group,
+                    None => {
+                        groups.push(name);
+                        groups.len() - 1
+                    }
+                }
+            })
+            .collect();
+        (groups, group_of)
+    }
+
+    fn layout_entry(&self, position: usize) -> Option<&OptionEntry<usize>> {
+        self.props
+            .state
+            .layout
+            .as_ref()
+            .and_then(|layout| layout.get(position))
+    }
+
+    /// Label and callback of an action row
+    fn action(&self, source: ActionSource) -> Option<(&str, &SelectAction)> {
+        let (label, callback) = match source {
+            ActionSource::Entry(position) => match self.layout_entry(position) {
+                Some(OptionEntry::Action { label, callback }) => (label, callback),
+                _ => return None,
+            },
+            ActionSource::Prop(position) => {
+                let (label, callback) = self.props.actions.get(position)?;
+                (label, callback)
+            }
+            ActionSource::Manage => {
+                let (label, callback) = self.props.manage_action.as_ref()?;
+                (label, callback)
+            }
+        };
+        Some((label.as_str(), callback))
+    }
+
+    /// Label of a header or action from the state's entries
+    fn entry_label(&self, position: usize) -> &str {
+        match self.layout_entry(position) {
+            Some(OptionEntry::Header(label)) | Some(OptionEntry::Action { label, .. }) => label,
+            _ => "",
+        }
+    }
+
+    fn group_label(&self, group: usize) -> &str {
+        self.groups
+            .get(group)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Options of `group` matching the search as `(index, selected)`, including
+    /// selected options hidden by `omit_selected`
+    fn group_members(&self, group: usize) -> Vec<(usize, bool)> {
+        self.matching_items()
+            .into_iter()
+            .filter(|&(index, _)| self.group_of.get(index) == Some(&group))
+            .collect()
+    }
+
+    /// Number of matching options in each group while searching (empty
+    /// otherwise). Counts every match, including those hidden by `max_results`.
+    fn group_match_counts(&self) -> Vec<usize> {
+        if self.search_text.is_empty() || self.groups.is_empty() {
+            return Vec::new();
+        }
+        let mut counts = vec![0; self.groups.len()];
+        for &(index, _) in self.visible_items().iter() {
+            if let Some(count) = self
+                .group_of
+                .get(index)
+                .and_then(|&group| counts.get_mut(group))
+            {
+                *count += 1;
+            }
+        }
+        counts
+    }
+
+    /// Match count after a group header, from `group_match_counts`
+    fn view_group_count(counts: &[usize], group: usize) -> Html {
+        match counts.get(group) {
+            Some(count) => html! {
+                <span class="has-text-grey has-text-weight-normal ml-1">{ format!("({})", count) }</span>
+            },
+            None => html! {},
+        }
+    }
+
+    /// Whether none, some or all of the group's matching options are selected
+    fn group_check(&self, group: usize) -> GroupCheck {
+        let members = self.group_members(group);
+        let selected = members.iter().filter(|&&(_, selected)| selected).count();
+        if selected == 0 {
+            GroupCheck::Unchecked
+        } else if selected == members.len() {
+            GroupCheck::Checked
+        } else {
+            GroupCheck::Partial
+        }
+    }
+
+    /// Whether the prompt should be shown instead of the (implicit) selection
+    fn is_prompting(&self) -> bool {
+        self.props.prompt.is_some()
+            && !self.props.state.is_nullable()
+            && self.props.state.is_pristine()
+    }
+
+    /// Label of the selected item in single mode (if not prompting)
+    fn selected_label(&self) -> Option<String> {
+        if self.is_prompting() {
+            None
+        } else {
+            self.props
+                .state
+                .first_selected()
+                .map(|(i, _)| self.label(i).to_string())
+                .or_else(|| {
+                    if self.has_invalid_selection() {
+                        Some(self.props.invalid_label.clone())
+                    } else {
+                        None
                     }
+                })
+        }
+    }
+
+    /// Whether a selection points at a missing option
+    fn has_invalid_selection(&self) -> bool {
+        !self.props.state.invalid_selections().is_empty()
+    }
+
+    /// Hover text of the selected item in single mode (if not prompting)
+    fn selected_tooltip(&self) -> Option<String> {
+        if self.is_prompting() {
+            None
+        } else {
+            self.props
+                .state
+                .first_selected()
+                .map(|(i, _)| self.tooltip(i))
+        }
+    }
+
+    fn is_checkbox_mode(&self) -> bool {
+        self.props.checkboxes && self.props.state.is_multiple()
+    }
+
+    /// Text copied to the clipboard for the current selection
+    fn selection_text(&self) -> String {
+        self.props
+            .state
+            .selected_items()
+            .into_iter()
+            .map(|(i, item)| match self.props.copy_format {
+                Some(ref format) => format.call(item),
+                None => self.label(i).to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(&self.props.copy_delimiter)
+    }
+
+    /// Resolve pasted tokens to option indices, returning the tokens that didn't match
+    /// Bulk entry dialog contents for `text`
+    fn resolve_bulk(&self, text: String) -> BulkEntry {
+        let (matched, unmatched) =
+            self.resolve_tokens(paste::split_tokens(&text, &self.props.paste_delimiters));
+        BulkEntry {
+            text,
+            matched,
+            unmatched,
+        }
+    }
+
+    fn resolve_tokens(&self, tokens: Vec<String>) -> (Vec<usize>, Vec<String>) {
+        let mut matched = Vec::new();
+        let mut unmatched = Vec::new();
+        for token in tokens {
+            let found = match self.props.paste_match {
+                PasteMatch::Exact => {
+                    let needle = token.to_lowercase();
+                    self.labels
+                        .iter()
+                        .position(|label| label.to_lowercase() == needle)
                 }
-                </div>
-                <div class="dropdown-menu">
-                    <div class="dropdown-content">
-                        { options }
+                PasteMatch::Filter => self.props.state.first_match(&token),
+            };
+            match found {
+                Some(index) if !matched.contains(&index) => matched.push(index),
+                Some(_) => {}
+                None => unmatched.push(token),
+            }
+        }
+        (matched, unmatched)
+    }
+
+    /// Option matching autofilled `text`, by `autofill_key` or else by label
+    /// (ignoring case)
+    fn resolve_autofill(&self, text: &str) -> Option<usize> {
+        let needle = text.trim().to_lowercase();
+        if needle.is_empty() {
+            return None;
+        }
+        let by_key = self.props.autofill_key.as_ref().and_then(|key| {
+            (0..self.labels.len()).find(|&index| {
+                self.props
+                    .state
+                    .get(index)
+                    .map_or(false, |item| key.call(item).to_lowercase() == needle)
+            })
+        });
+        by_key.or_else(|| {
+            self.labels
+                .iter()
+                .position(|label| label.to_lowercase() == needle)
+        })
+    }
+
+    fn selected_indices(state: &SelectState<T>) -> Vec<usize> {
+        state
+            .selected_items()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Report the selection through `onchange` and `onchange_values`
+    fn emit_change(&self) {
+        let values = self
+            .reported_selection
+            .iter()
+            .map(
+                |&index| match (&self.props.change_value, self.props.state.get(index)) {
+                    (Some(change_value), Some(item)) => change_value.call(item),
+                    _ => self.label(index).to_string(),
+                },
+            )
+            .collect::<Vec<_>>();
+        if let Some(ref onchange) = self.props.onchange {
+            onchange.emit(ChangeData::Value(
+                values.first().cloned().unwrap_or_default(),
+            ));
+        }
+        if let Some(ref onchange_values) = self.props.onchange_values {
+            onchange_values.emit(values);
+        }
+    }
+
+    /// Emit several selections at once, through `onselectedmany` if available
+    fn emit_selected_many(&mut self, indices: Vec<usize>) {
+        let indices = self.limit_picks(indices);
+        if indices.is_empty() {
+            return;
+        }
+        if let Some(ref onselectedmany) = self.props.onselectedmany {
+            onselectedmany.emit(indices);
+        } else if let Some(ref onselected) = self.props.onselected {
+            for index in indices {
+                onselected.emit(index);
+            }
+        }
+    }
+
+    /// How many more options can be selected before `max_selected` is reached
+    fn remaining_picks(&self) -> Option<usize> {
+        match self.props.max_selected {
+            Some(max_selected) if self.props.state.is_multiple() => {
+                Some(max_selected.saturating_sub(self.props.state.selected_items().len()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Drop disabled options, and the picks beyond `max_selected` (showing the
+    /// limit notice if any were)
+    fn limit_picks(&mut self, mut indices: Vec<usize>) -> Vec<usize> {
+        indices.retain(|&index| !self.props.state.is_disabled(index));
+        if let Some(remaining) = self.remaining_picks() {
+            if indices.len() > remaining {
+                indices.truncate(remaining);
+                self.limit_reached = true;
+            }
+        }
+        indices
+    }
+
+    /// Notice shown (and announced) when a pick was blocked by `max_selected`
+    fn limit_notice(&self) -> Option<String> {
+        match self.props.max_selected {
+            Some(max_selected) if self.limit_reached => {
+                Some(format!("Maximum of {} selections", max_selected))
+            }
+            _ => None,
+        }
+    }
+
+    /// Emit several removals at once, through `onremovedmany` if available
+    fn emit_removed_many(&self, indices: Vec<usize>) {
+        if let Some(ref onremovedmany) = self.props.onremovedmany {
+            onremovedmany.emit(indices);
+        } else if let Some(ref onremoved) = self.props.onremoved {
+            for index in indices {
+                onremoved.emit(index);
+            }
+        }
+    }
+
+    /// Highlight the row at `position` and select every option between it and
+    /// the range anchor (which is set to the current highlight if not started)
+    fn select_range(&mut self, position: usize) {
+        let rows = self.rows();
+        if rows.is_empty() {
+            return;
+        }
+        let position = position.min(rows.len() - 1);
+        let anchor = *self.range_anchor.get_or_insert(self.highlighted());
+        let (start, end) = (
+            anchor.min(position),
+            anchor.max(position).min(rows.len() - 1),
+        );
+
+        let indices = rows[start..=end]
+            .iter()
+            .filter_map(|row| match *row {
+                Row::Item(index, false) => Some(index),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        self.selection_index = position;
+        self.hover_index = None;
+        if !indices.is_empty() {
+            self.emit_selected_many(indices);
+        }
+    }
+
+    /// Commit the highlighted row (as if clicked)
+    fn commit_highlighted(&self) {
+        match self.rows().get(self.highlighted()) {
+            Some(Row::None) => self.link.send_message(Msg::ClearSelection),
+            Some(&Row::Item(index, _)) => self.link.send_message(Msg::Selected(index)),
+            Some(&Row::Group(group)) if self.props.state.is_multiple() => {
+                self.link.send_message(Msg::ToggleGroup(group))
+            }
+            Some(&Row::Group(group)) => self.link.send_message(Msg::ToggleCollapsed(group)),
+            Some(&Row::Action(source)) => self.link.send_message(Msg::Action(source)),
+            Some(Row::ShowMore(_)) => self.link.send_message(Msg::ShowMore),
+            Some(&Row::Suggestion(position)) => {
+                if let Some(suggestion) = self.suggestions.get(position) {
+                    self.link.send_message(Msg::Input(suggestion.clone()))
+                }
+            }
+            Some(Row::Divider) | Some(Row::Header(_)) | None => {}
+        }
+    }
+
+    /// `target` (e.g. where focus moved) is inside this select
+    fn contains(&self, target: Option<EventTarget>) -> bool {
+        match (self.dropdown_ref.cast::<Node>(), target) {
+            (Some(dropdown), Some(target)) => target
+                .dyn_into::<Node>()
+                .map_or(false, |node| dropdown.contains(Some(&node))),
+            _ => false,
+        }
+    }
+
+    /// The focused element is inside this select
+    fn has_focus_within(&self) -> bool {
+        let active = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.active_element());
+        self.contains(active.map(EventTarget::from))
+    }
+
+    fn scroll_parent(&self) -> Option<Element> {
+        self.props
+            .scroll_parent
+            .as_ref()
+            .and_then(|parent| parent.cast::<Element>())
+    }
+
+    /// Whether the menu should open upwards (`None` if it isn't rendered)
+    fn measure_drop_up(&self) -> Option<bool> {
+        let dropdown = self.dropdown_ref.cast::<Element>()?;
+        let menu = self.menu_ref.cast::<Element>()?;
+        Some(placement::drop_up(
+            &dropdown,
+            &menu,
+            self.scroll_parent().as_ref(),
+        ))
+    }
+
+    /// The menu is animated with the enter/leave classes
+    fn animated(&self) -> bool {
+        !self.props.disable_animations && !self.reduced_motion
+    }
+
+    /// Close the dropdown. With a `menu_leave_class`, the menu stays shown with
+    /// it for `menu_leave_ms`.
+    fn close(&mut self) {
+        if self.focused && self.animated() && !self.props.menu_leave_class.is_empty() {
+            self.leaving = true;
+            self.leave_generation += 1;
+            let generation = self.leave_generation;
+            let link = self.link.clone();
+            timeout::set_timeout(self.props.menu_leave_ms, move || {
+                link.send_message(Msg::Left(generation))
+            });
+        }
+        self.focused = false;
+    }
+
+    /// Open the dropdown. In single mode, the current selection is highlighted
+    /// (so Enter doesn't re-select the first option).
+    fn open(&mut self) {
+        self.focused = true;
+        let rows = self.row_kinds();
+        self.selection_index = self.menu(&rows).opening_highlight();
+        self.scroll_to_highlight = true;
+    }
+
+    /// The dropdown as the interaction logic in [`core`](crate::core) sees it
+    fn menu<'a>(&self, rows: &'a [crate::core::RowKind]) -> crate::core::Menu<'a> {
+        crate::core::Menu {
+            rows,
+            selected: if self.props.state.is_multiple() {
+                None
+            } else {
+                self.selected_position()
+            },
+            enter_commits: self.props.enter_commits,
+        }
+    }
+
+    /// Move the highlight (or hover) with an input of the shared reducer, for
+    /// the inputs which only move it (the arrow keys while open, Home/End and
+    /// the pointer)
+    fn navigate(&mut self, input: crate::core::Input) {
+        let rows = self.row_kinds();
+        let interaction = crate::core::Interaction {
+            open: self.focused,
+            highlight: self.selection_index,
+            hover: self.hover_index,
+            query: self.search_text.clone(),
+        };
+        let (interaction, _) = crate::core::reduce(interaction, input, &self.menu(&rows));
+        self.selection_index = interaction.highlight;
+        self.hover_index = interaction.hover;
+    }
+
+    /// Position of the (first) selected item in the dropdown rows
+    fn selected_position(&self) -> Option<usize> {
+        self.rows()
+            .iter()
+            .position(|row| matches!(row, Row::Item(_, true)))
+    }
+
+    /// Attach the highlight ref to the row at `position` if it is highlighted
+    fn row_ref(&self, position: usize) -> NodeRef {
+        if self.highlighted() == position {
+            self.highlight_ref.clone()
+        } else {
+            NodeRef::default()
+        }
+    }
+
+    /// Position of the highlighted row, according to `enter_commits` (as
+    /// `core::Interaction::highlighted`)
+    fn highlighted(&self) -> usize {
+        match (self.props.enter_commits, self.hover_index) {
+            (HighlightSource::Pointer, Some(index)) => index,
+            _ => self.selection_index,
+        }
+    }
+
+    fn has_none_row(&self) -> bool {
+        self.props.none_label.is_some()
+            && self.props.state.is_nullable()
+            && !self.props.state.is_multiple()
+    }
+
+    /// Rows of the dropdown, in display order
+    fn rows(&self) -> Vec<Row> {
+        let cached = self.visible_items();
+        let visible: Cow<[(usize, bool)]> = if self.props.selected_first {
+            let (selected, unselected): (Vec<_>, Vec<_>) =
+                cached.iter().partition(|&&(_, selected)| selected);
+            Cow::Owned(selected.into_iter().chain(unselected).collect())
+        } else {
+            Cow::Borrowed(&cached[..])
+        };
+        let limit = match self.props.max_results {
+            Some(max_results) if !self.expanded => max_results.min(visible.len()),
+            _ => visible.len(),
+        };
+        let (items, hidden) = (&visible[..limit], visible.len() - limit);
+        let mut rows = Vec::with_capacity(items.len() + 1);
+        if self.has_none_row() && !items.is_empty() {
+            rows.push(Row::None);
+        }
+        if let Some(ref layout) = self.props.state.layout {
+            let mut shown = vec![None; self.props.state.options.len()];
+            for &(index, selected) in items.iter() {
+                shown[index] = Some(selected);
+            }
+            rows.extend(layout.iter().enumerate().filter_map(|(position, entry)| {
+                match *entry {
+                    OptionEntry::Item(index) => shown
+                        .get(index)
+                        .copied()
+                        .flatten()
+                        .map(|selected| Row::Item(index, selected)),
+                    OptionEntry::Header(_) => Some(Row::Header(position)),
+                    OptionEntry::Divider => Some(Row::Divider),
+                    OptionEntry::Action { .. } => Some(Row::Action(ActionSource::Entry(position))),
+                }
+            }));
+        } else if self.props.group_by.is_some() {
+            let mut grouped = vec![Vec::new(); self.groups.len()];
+            for &(index, selected) in items.iter() {
+                if let Some(group) = grouped.get_mut(self.group_of.get(index).copied().unwrap_or(0))
+                {
+                    group.push(Row::Item(index, selected));
+                }
+            }
+            for (group, members) in grouped.into_iter().enumerate() {
+                if !members.is_empty() {
+                    rows.push(Row::Group(group));
+                    if !self.is_collapsed(group) {
+                        rows.extend(members);
+                    }
+                }
+            }
+        } else {
+            // Items are only sorted by selection with `selected_first`
+            let picked = if self.props.selected_first {
+                items.iter().take_while(|&&(_, selected)| selected).count()
+            } else {
+                0
+            };
+            rows.extend(
+                items[..picked]
+                    .iter()
+                    .map(|&(index, selected)| Row::Item(index, selected)),
+            );
+            if picked > 0 {
+                rows.push(Row::Divider);
+            }
+            rows.extend(
+                items[picked..]
+                    .iter()
+                    .map(|&(index, selected)| Row::Item(index, selected)),
+            );
+        }
+
+        if let Some(ref divider_after) = self.props.divider_after {
+            let mut divided = Vec::with_capacity(rows.len());
+            for row in rows {
+                divided.push(row);
+                if let Row::Item(index, _) = row {
+                    if self
+                        .props
+                        .state
+                        .get(index)
+                        .map_or(false, |item| divider_after.call(item))
+                    {
+                        divided.push(Row::Divider);
+                    }
+                }
+            }
+            rows = divided;
+        }
+
+        if hidden > 0 {
+            rows.push(Row::ShowMore(hidden));
+        }
+        rows.extend((0..self.suggestions.len()).map(Row::Suggestion));
+
+        if !self.props.actions.is_empty() {
+            rows.push(Row::Divider);
+            rows.extend(
+                (0..self.props.actions.len())
+                    .map(|position| Row::Action(ActionSource::Prop(position))),
+            );
+        }
+        if self.props.manage_action.is_some() {
+            rows.push(Row::Divider);
+            rows.push(Row::Action(ActionSource::Manage));
+        }
+        tidy_rows(rows)
+    }
+
+    /// An action row at `position` in the dropdown rows
+    fn view_action<F: Fn() -> Msg + 'static>(
+        &self,
+        position: usize,
+        key: String,
+        label: &str,
+        pick: F,
+    ) -> Html {
+        html! {
+            <a
+                key=key.clone()
+                ref=self.row_ref(position)
+                class=classes!(
+                    self.props.theme.item.clone(),
+                    self.props.item_class.clone(),
+                    if self.highlighted() == position { Some(self.active_item_class()) } else { None }
+                )
+                data-testid=self.test_id(&key)
+            >
+                <p
+                    onmouseenter=self.link.callback(move |_| Msg::Hover(position))
+                    onmousedown=self.link.callback(move |event: MouseEvent| {
+                        let event: &Event = &event;
+                        event.prevent_default();
+                        pick()
+                    })
+                >
+                    { label }
+                </p>
+            </a>
+        }
+    }
+
+    /// How the interaction logic in [`core`](crate::core) sees `row`
+    fn row_kind(&self, row: &Row) -> crate::core::RowKind {
+        use crate::core::RowKind;
+        match row {
+            Row::Item(index, _) if self.props.state.is_disabled(*index) => RowKind::Inert,
+            Row::Item(index, _) => RowKind::Option(*index),
+            Row::Divider | Row::Header(_) => RowKind::Inert,
+            // Single mode headers can only be collapsed
+            Row::Group(_) if !self.props.state.is_multiple() && !self.props.collapsible_groups => {
+                RowKind::Inert
+            }
+            Row::None | Row::Group(_) | Row::Action(_) | Row::Suggestion(_) | Row::ShowMore(_) => {
+                RowKind::Other
+            }
+        }
+    }
+
+    fn row_kinds(&self) -> Vec<crate::core::RowKind> {
+        self.rows().iter().map(|row| self.row_kind(row)).collect()
+    }
+
+    /// Position of the next navigable row after (or before) `position`, staying
+    /// put if there is none
+    fn step(&self, position: usize, forward: bool) -> usize {
+        crate::core::step(&self.row_kinds(), position, forward)
+    }
+
+    fn is_visible_current(&self) -> bool {
+        let generation = self.props.state.generation();
+        matches!(*self.visible.borrow(), Some((cached, _)) if cached == generation)
+    }
+
+    /// Why no options are shown (when the dropdown is empty)
+    fn empty_state(&self) -> EmptyState {
+        if self.props.state.options.is_empty() || self.search_text.is_empty() {
+            EmptyState::Empty
+        } else {
+            EmptyState::NoMatches {
+                query: self.search_text.clone(),
+            }
+        }
+    }
+
+    /// Options are hidden until a query is typed (without `show_all_on_focus`)
+    fn is_awaiting_query(&self) -> bool {
+        !self.props.show_all_on_focus && self.search_text.is_empty()
+    }
+
+    /// `(index, selected)` for every option matching the search
+    fn matching_items(&self) -> Vec<(usize, bool)> {
+        let state = &self.props.state;
+        match self.filtered {
+            // The state's predicate may have changed since the search ran
+            Some(ref indices) => state.with_selected(state.allowed(indices.iter().copied())),
+            None if self.is_awaiting_query() => Vec::new(),
+            None => state.with_selected(state.allowed(0..state.options.len())),
+        }
+    }
+
+    /// Visible `(index, selected)` pairs, only recomputed when the state or search has changed
+    fn visible_items(&self) -> Rc<Vec<(usize, bool)>> {
+        if !self.is_visible_current() {
+            let generation = self.props.state.generation();
+            let mut items = self
+                .matching_items()
+                .into_iter()
+                .map(|(index, selected)| (index, selected && !self.is_prompting()))
+                .filter(|(_, selected)| !(self.props.omit_selected && *selected))
+                .collect::<Vec<_>>();
+            self.props.state.pinned_first(&mut items);
+            self.visible.replace(Some((generation, Rc::new(items))));
+        }
+
+        self.visible
+            .borrow()
+            .as_ref()
+            .map(|(_, items)| items.clone())
+            .unwrap_or_default()
+    }
+
+    /// Cached display string for the option at `index`
+    fn label(&self, index: usize) -> &str {
+        self.labels
+            .get(index)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Image for the option at `index`, if `avatar` gives one
+    fn view_avatar(&self, index: usize, class: Classes) -> Html {
+        match (&self.props.avatar, self.props.state.get(index)) {
+            (Some(avatar), Some(item)) => match avatar.call(item) {
+                Some(src) => html! {
+                    <span class=class>
+                        <img class="is-rounded" src=src alt="" />
+                    </span>
+                },
+                None => html! {},
+            },
+            _ => html! {},
+        }
+    }
+
+    /// Panel beside the menu with the `preview` of the hovered (or else highlighted) option
+    fn view_preview(&self, rows: &[Row]) -> Html {
+        let preview = match self.props.preview {
+            Some(ref preview) => preview,
+            None => return html! {},
+        };
+        let position = self.hover_index.unwrap_or(self.selection_index);
+        match rows.get(position) {
+            Some(&Row::Item(index, _)) => match self.props.state.get(index) {
+                Some(item) => html! {
+                    <div
+                        class=self.props.theme.preview.clone()
+                        style="position: absolute; top: 0; left: 100%; min-width: 16rem"
+                        data-testid=self.test_id("preview")
+                    >
+                        { preview.call(item) }
                     </div>
-                </div>
-            </div>
+                },
+                None => html! {},
+            },
+            _ => html! {},
         }
     }
-}
 
-impl<T> Select<T> {
+    /// Button at the end of the row of the option at `index` (with `onoptionaction`)
+    fn view_option_action(&self, index: usize) -> Html {
+        if self.props.onoptionaction.is_none() {
+            return html! {};
+        }
+        html! {
+            <button
+                type="button"
+                class=self.props.theme.item_action.clone()
+                tabindex="-1"
+                aria-label=format!("{} {}", self.props.option_action_label, self.label(index))
+                data-testid=self.test_id(&format!("item-action-{}", index))
+                onmousedown=self.link.callback(move |event: MouseEvent| {
+                    // Keep the row from selecting the option (and the input from blurring)
+                    let event: &Event = &event;
+                    event.stop_propagation();
+                    event.prevent_default();
+                    Msg::OptionAction(index)
+                })
+            >
+                <span class="icon is-small">
+                    <i class=self.props.option_action_icon.clone() />
+                </span>
+            </button>
+        }
+    }
+
+    /// Tag at the end of the row of the option at `index`, from its metadata or `badge`
+    fn view_badge(&self, index: usize) -> Html {
+        let badge = self
+            .props
+            .state
+            .meta(index)
+            .and_then(|meta| meta.badge)
+            .or_else(|| {
+                let badge = self.props.badge.as_ref()?;
+                badge.call(self.props.state.get(index)?)
+            });
+        match badge {
+            Some(badge) => html! {
+                <span class=self.props.theme.badge.clone()>{ badge }</span>
+            },
+            None => html! {},
+        }
+    }
+
+    /// Image of the selected item in single mode (if not prompting), as a left icon
+    fn view_selected_avatar(&self) -> Html {
+        match self.props.state.first_selected() {
+            Some((index, _)) if self.props.avatar.is_some() && !self.is_prompting() => html! {
+                <span class="icon is-left">
+                    { self.view_avatar(index, self.props.theme.tag_avatar.clone()) }
+                </span>
+            },
+            _ => html! {},
+        }
+    }
+
+    fn single_control_classes(&self) -> Classes {
+        let avatar = self.props.avatar.is_some()
+            && !self.is_prompting()
+            && self.props.state.first_selected().is_some();
+        classes!(
+            "control",
+            "has-icons-right",
+            if avatar { Some("has-icons-left") } else { None }
+        )
+    }
+
+    /// Global indices of the selected items shown as tags
+    fn tag_indices(&self) -> Vec<usize> {
+        if self.props.state.is_multiple() && self.props.display_selected {
+            self.props
+                .state
+                .selected_items()
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Whether the search input's caret is at the start (so Left can move to the tags)
+    fn caret_at_start(&self) -> bool {
+        self.input_ref
+            .cast::<HtmlInputElement>()
+            .and_then(|input| input.selection_start().ok().flatten())
+            .map_or(true, |position| position == 0)
+    }
+
+    /// Remember the search input's caret, if it is being edited
+    fn save_caret(&mut self) {
+        if !self.has_focus {
+            return;
+        }
+        self.caret = self.input_ref.cast::<HtmlInputElement>().and_then(|input| {
+            let start = input.selection_start().ok().flatten()?;
+            let end = input.selection_end().ok().flatten()?;
+            Some((start, end))
+        });
+    }
+
+    /// Put the caret back where `save_caret` found it, if the input still has
+    /// the focus (moving the caret of an unfocused input can steal the focus)
+    fn restore_caret(&self, start: u32, end: u32) {
+        if !self.has_focus {
+            return;
+        }
+        if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+            // Positions are in UTF-16 code units, like the DOM's
+            let len = input.value().encode_utf16().count() as u32;
+            let (start, end) = (start.min(len), end.min(len));
+            if input.selection_start().ok().flatten() != Some(start)
+                || input.selection_end().ok().flatten() != Some(end)
+            {
+                let _ = input.set_selection_range(start, end);
+            }
+        }
+    }
+
+    /// Hover text for the option at `index`
+    fn tooltip(&self, index: usize) -> String {
+        match (&self.props.tooltip, self.props.state.get(index)) {
+            (Some(tooltip), Some(item)) => tooltip.call(item),
+            _ => self.label(index).to_string(),
+        }
+    }
+
+    /// `autocomplete` of the search input (the `autofill` token, if set)
+    fn autocomplete(&self) -> String {
+        match self.props.autofill {
+            Some(autofill) => autofill.token().to_string(),
+            None => self.props.autocomplete.clone(),
+        }
+    }
+
+    fn input_classes(&self) -> Classes {
+        let theme = &self.props.theme;
+        classes!(
+            theme.input.clone(),
+            if self.props.loading {
+                Some(theme.loading.clone())
+            } else {
+                None
+            },
+            if !self.props.state.is_multiple() && self.has_invalid_selection() {
+                Some(theme.invalid_input.clone())
+            } else {
+                None
+            },
+            if self.props.error.is_some() {
+                Some(theme.error_input.clone())
+            } else {
+                None
+            }
+        )
+    }
+
+    /// Build a `data-testid` value for a part of the select (if `test_id` is set)
+    fn test_id(&self, part: &str) -> Option<String> {
+        self.props
+            .test_id
+            .as_ref()
+            .map(|id| format!("{}-{}", id, part))
+    }
+
+    fn active_item_class(&self) -> Classes {
+        let class = self
+            .props
+            .active_item_class
+            .clone()
+            .unwrap_or_else(|| self.props.theme.active_item.clone());
+        if self.keyboard {
+            classes!(class, self.props.theme.focus_visible.clone())
+        } else {
+            class
+        }
+    }
+
+    fn selected_item_class(&self) -> Classes {
+        self.props
+            .selected_item_class
+            .clone()
+            .unwrap_or_else(|| self.props.theme.selected_item.clone())
+    }
+
     fn view_single(&self) -> Html {
-        if self.focused {
+        let accepts_autofill = self.props.autofill.is_some();
+        // Show the search box while typing is possible, even if the menu isn't open yet
+        if self.focused || self.has_focus {
             html! {
-                <div class="control has-icons-right">
+                <div class=self.single_control_classes()>
+                    { self.view_selected_avatar() }
                     <input
-                        class=classes!("input", if self.props.loading {"is-loading"} else {""})
+                        ref=self.input_ref.clone()
+                        class=self.input_classes()
+                        data-testid=self.test_id("input")
                         type="text"
+                        id=self.props.input_id.clone()
+                        autocomplete=self.autocomplete()
+                        inputmode=self.props.inputmode.clone()
+                        enterkeyhint=self.props.enterkeyhint.clone()
+                        aria-invalid=self.props.error.as_ref().map(|_| String::from("true"))
+                        aria-describedby=self.props.error.as_ref().map(|_| self.error_id.clone())
+                        autocorrect=self.props.autocorrect.clone()
+                        autocapitalize=self.props.autocapitalize.clone()
+                        spellcheck=self.props.spellcheck.map(|spellcheck| spellcheck.to_string())
+                        maxlength=self.props.maxlength.map(|maxlength| maxlength.to_string())
                         value=&self.search_text
-                        placeholder=self.props.state.selected_items().first().map(|(_, x)| self.props.display.call(x)).unwrap_or_else(|| self.props.placeholder.clone())
-                        oninput=self.link.callback(|event: InputData| Msg::Input(event.value))
+                        placeholder=self.selected_label().unwrap_or_else(|| self.props.placeholder.clone())
+                        oninput=self.link.callback(move |data: InputData| {
+                            if accepts_autofill && autofill::is_autofill(&data.event) {
+                                Msg::Autofill(data.value)
+                            } else {
+                                Msg::Input(data.value)
+                            }
+                        })
                         onfocus=self.link.callback(|_| Msg::Focus)
-                        onblur=self.link.callback(|_| Msg::Blur)
+                        onclick=self.link.callback(|_| Msg::Click)
+                        onblur=self.link.callback(Msg::InputBlur)
                         onkeydown=self.link.callback(Msg::KeyPress)
                         disabled=self.props.disabled
                         readonly=self.props.readonly
@@ -340,7 +3297,7 @@ impl<T> Select<T> {
                         if self.search_text.is_empty() {
                             html! { <i class="fas fa-search" /> }
                         } else {
-                            html! {<button class="delete" onclick=self.link.callback(|_| Msg::ClearSearch) /> }
+                            html! {<button class=self.props.theme.delete.clone() onclick=self.link.callback(|_| Msg::ClearSearch) /> }
                         }
                     }
                     </span>
@@ -348,20 +3305,39 @@ impl<T> Select<T> {
             }
         } else {
             html! {
-                <div class="control has-icons-right">
+                <div class=self.single_control_classes()>
+                    { self.view_selected_avatar() }
                     <input
-                        class=classes!("input", if self.props.loading {"is-loading"} else {""})
+                        ref=self.input_ref.clone()
+                        class=self.input_classes()
+                        data-testid=self.test_id("input")
                         type="text"
-                        value=self.props.state.selected_items().first().map(|(_, x)| self.props.display.call(x)).unwrap_or_default()
-                        oninput=self.link.callback(|data: InputData| {
-                            // Don't allow input when not focused
+                        id=self.props.input_id.clone()
+                        autocomplete=self.autocomplete()
+                        inputmode=self.props.inputmode.clone()
+                        enterkeyhint=self.props.enterkeyhint.clone()
+                        aria-invalid=self.props.error.as_ref().map(|_| String::from("true"))
+                        aria-describedby=self.props.error.as_ref().map(|_| self.error_id.clone())
+                        autocorrect=self.props.autocorrect.clone()
+                        autocapitalize=self.props.autocapitalize.clone()
+                        spellcheck=self.props.spellcheck.map(|spellcheck| spellcheck.to_string())
+                        maxlength=self.props.maxlength.map(|maxlength| maxlength.to_string())
+                        value=self.selected_label().unwrap_or_default()
+                        title=self.selected_tooltip()
+                        placeholder=self.props.prompt.clone().unwrap_or_default()
+                        oninput=self.link.callback(move |data: InputData| {
+                            // Values filled in by the browser are picked, but
+                            // typing isn't allowed when not focused
+                            if accepts_autofill && autofill::is_autofill(&data.event) {
+                                return Msg::Autofill(data.value);
+                            }
                             let event: &Event = &data.event;
                             event.prevent_default();
                             Msg::Focus
                         })
                         onfocus=self.link.callback(|_| Msg::Focus)
-                        onblur=self.link.callback(|_| Msg::Blur)
-                        onclick=self.link.callback(|_| Msg::Focus)
+                        onblur=self.link.callback(Msg::InputBlur)
+                        onclick=self.link.callback(|_| Msg::Click)
                         onkeydown=self.link.callback(Msg::KeyPress)
                         disabled=self.props.disabled
                         readonly=self.props.readonly
@@ -375,14 +3351,52 @@ impl<T> Select<T> {
     }
 
     fn view_multiple(&self) -> Html {
+        let delimiters = self.props.paste_delimiters.clone();
+        let accepts_autofill = self.props.autofill.is_some();
         html! {
-            <div class=classes!("input", "ybss-multiple-input-wrapper", if self.focused {"is-active"} else {""})>
+            <div class=classes!(self.props.theme.input.clone(), "ybss-multiple-input-wrapper", if self.focused { Some(self.props.theme.open.clone()) } else { None })>
+                {
+                    if self.props.display_selected {
+                        self.props.state.selected_items().into_iter().enumerate().map(|(position, (i, _))| html! {
+                            <span
+                                key=i.to_string()
+                                class=classes!(
+                                    self.props.theme.tag.clone(),
+                                    self.props.tag_class_extra.clone(),
+                                    if self.active_tag == Some(position) { Some(classes!(self.props.theme.active_tag.clone(), self.props.theme.focus_visible.clone())) } else { None }
+                                )
+                                data-testid=self.test_id(&format!("tag-{}", i))
+                                title=self.tooltip(i)
+                            >
+                                { self.view_avatar(i, self.props.theme.tag_avatar.clone()) }
+                                { self.label(i) }
+                                <button
+                                    type="button"
+                                    class=self.props.theme.tag_delete.clone()
+                                    aria-label=format!("Remove {}", self.label(i))
+                                    onclick=self.link.callback(move |_| Msg::Removed(i))
+                                />
+                            </span>
+                        }).collect::<Html>()
+                    } else {
+                        html! {}
+                    }
+                }
                 {
                     if self.props.display_selected {
-                        self.props.state.selected_items().into_iter().map(|(i, item)| html! {
-                            <span class="tag">
-                                { self.props.display.call(&item) }
-                                <div class="delete is-small" onclick=self.link.callback(move |_| Msg::Removed(i)) />
+                        self.props.state.invalid_selections().into_iter().map(|i| html! {
+                            <span
+                                key=format!("invalid-{}", i)
+                                class=classes!(self.props.theme.invalid_tag.clone(), self.props.tag_class_extra.clone())
+                                data-testid=self.test_id(&format!("invalid-tag-{}", i))
+                            >
+                                { &self.props.invalid_label }
+                                <button
+                                    type="button"
+                                    class=self.props.theme.tag_delete.clone()
+                                    aria-label=format!("Remove {}", self.props.invalid_label)
+                                    onclick=self.link.callback(move |_| Msg::Removed(i))
+                                />
                             </span>
                         }).collect::<Html>()
                     } else {
@@ -390,17 +3404,123 @@ impl<T> Select<T> {
                     }
                 }
                 <input
-                    class=classes!("input", if self.props.loading {"is-loading"} else {""})
+                    ref=self.input_ref.clone()
+                    class=self.input_classes()
+                    data-testid=self.test_id("input")
                     type="text"
+                    id=self.props.input_id.clone()
+                    autocomplete=self.autocomplete()
+                    inputmode=self.props.inputmode.clone()
+                    enterkeyhint=self.props.enterkeyhint.clone()
+                    aria-invalid=self.props.error.as_ref().map(|_| String::from("true"))
+                    aria-describedby=self.props.error.as_ref().map(|_| self.error_id.clone())
+                    autocorrect=self.props.autocorrect.clone()
+                    autocapitalize=self.props.autocapitalize.clone()
+                    spellcheck=self.props.spellcheck.map(|spellcheck| spellcheck.to_string())
+                    maxlength=self.props.maxlength.map(|maxlength| maxlength.to_string())
                     placeholder="Type to search"
                     value=&self.search_text
-                    oninput=self.link.callback(|event: InputData| Msg::Input(event.value))
+                    oninput=self.link.callback(move |data: InputData| {
+                        if accepts_autofill && autofill::is_autofill(&data.event) {
+                            Msg::Autofill(data.value)
+                        } else {
+                            Msg::Input(data.value)
+                        }
+                    })
                     onfocus=self.link.callback(|_| Msg::Focus)
-                    onblur=self.link.callback(|_| Msg::Blur)
+                    onclick=self.link.callback(|_| Msg::Click)
+                    onblur=self.link.callback(Msg::InputBlur)
                     onkeydown=self.link.callback(Msg::KeyPress)
+                    onpaste=self.link.callback(move |event: Event| {
+                        match clipboard::pasted_text(&event) {
+                            Some(text) if text.contains(|c| delimiters.contains(&c)) => {
+                                event.prevent_default();
+                                Msg::Paste(text)
+                            }
+                            _ => Msg::Noop,
+                        }
+                    })
                     disabled=self.props.disabled
                     readonly=self.props.readonly
                 />
+                {
+                    if self.props.bulk_entry && !self.props.disabled && !self.props.readonly {
+                        html! {
+                            <button
+                                class=self.props.theme.bulk_button.clone()
+                                title="Paste a list"
+                                data-testid=self.test_id("bulk")
+                                onclick=self.link.callback(|_| Msg::OpenBulk)
+                            >
+                                <span class="icon is-small"><i class="fas fa-paste" /></span>
+                            </button>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+
+    fn view_bulk_entry(&self) -> Html {
+        let BulkEntry {
+            ref text,
+            ref matched,
+            ref unmatched,
+        } = match self.bulk {
+            Some(ref bulk) => bulk,
+            None => return html! {},
+        };
+        let theme = &self.props.theme;
+
+        html! {
+            <div class=theme.bulk_dialog.clone() data-testid=self.test_id("bulk-dialog")>
+                <div class="modal-background" onclick=self.link.callback(|_| Msg::CloseBulk) />
+                <div class="modal-card">
+                    <header class="modal-card-head">
+                        <p class="modal-card-title">{"Paste a list"}</p>
+                        <button class=theme.delete.clone() onclick=self.link.callback(|_| Msg::CloseBulk) />
+                    </header>
+                    <section class="modal-card-body">
+                        <div class="field">
+                            <textarea
+                                class=theme.bulk_textarea.clone()
+                                rows="8"
+                                value=text
+                                oninput=self.link.callback(|event: InputData| Msg::BulkInput(event.value))
+                            />
+                        </div>
+                        <p>{ format!("{} matched", matched.len()) }</p>
+                        <div class="tags">
+                            { for matched.iter().map(|&i| html! { <span class=theme.bulk_matched.clone()>{ self.label(i) }</span> }) }
+                        </div>
+                        {
+                            if unmatched.is_empty() {
+                                html! {}
+                            } else {
+                                html! {
+                                    <>
+                                        <p>{ format!("{} not found", unmatched.len()) }</p>
+                                        <div class="tags">
+                                            { for unmatched.iter().map(|token| html! { <span class=theme.bulk_unmatched.clone()>{ token }</span> }) }
+                                        </div>
+                                    </>
+                                }
+                            }
+                        }
+                    </section>
+                    <footer class="modal-card-foot">
+                        <button
+                            class=theme.bulk_apply.clone()
+                            disabled=matched.is_empty()
+                            onclick=self.link.callback(|_| Msg::ApplyBulk)
+                        >
+                            { format!("Select {}", matched.len()) }
+                        </button>
+                        <button class=theme.bulk_cancel.clone() onclick=self.link.callback(|_| Msg::CloseBulk)>{"Cancel"}</button>
+                    </footer>
+                </div>
             </div>
         }
     }
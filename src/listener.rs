@@ -0,0 +1,37 @@
+//! Event listeners on the window, for events outside of the component's markup
+
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Listeners for `events` (names, and whether to listen in the capture phase)
+/// on the window, removed when dropped
+pub(crate) struct WindowListener {
+    events: &'static [(&'static str, bool)],
+    callback: Closure<dyn Fn()>,
+}
+
+impl WindowListener {
+    pub(crate) fn new(
+        events: &'static [(&'static str, bool)],
+        callback: impl Fn() + 'static,
+    ) -> Option<Self> {
+        let window = web_sys::window()?;
+        let callback = Closure::wrap(Box::new(callback) as Box<dyn Fn()>);
+        let function = callback.as_ref().unchecked_ref();
+        for &(event, capture) in events {
+            let _ = window.add_event_listener_with_callback_and_bool(event, function, capture);
+        }
+        Some(Self { events, callback })
+    }
+}
+
+impl Drop for WindowListener {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            let function = self.callback.as_ref().unchecked_ref();
+            for &(event, capture) in self.events {
+                let _ =
+                    window.remove_event_listener_with_callback_and_bool(event, function, capture);
+            }
+        }
+    }
+}
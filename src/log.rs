@@ -0,0 +1,17 @@
+//! Debug instrumentation. Events are only emitted (via `tracing`) when the
+//! `log` feature is enabled, otherwise the macros compile to nothing.
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        tracing::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            // Keep the arguments "used" so disabling the feature doesn't cause warnings
+            let _ = format_args!($($arg)*);
+        }
+    };
+}
+pub(crate) use debug;
+
+pub(crate) use search_select_core::log::now;
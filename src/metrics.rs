@@ -0,0 +1,11 @@
+/// Performance measurements emitted by the `onmetrics` callback after each render
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectMetrics {
+    /// Duration of the most recent filter run, in milliseconds
+    pub filter_duration: Option<f64>,
+    /// Number of options rendered in the dropdown (after `omit_selected` and
+    /// `max_results`; headers, dividers and actions aren't counted)
+    pub rendered_items: usize,
+    /// Total number of renders of this component
+    pub render_count: usize,
+}
@@ -0,0 +1,13 @@
+//! The user's motion preference
+
+/// The user asked the system to minimize animations (`prefers-reduced-motion`)
+pub(crate) fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .map_or(false, |query| query.matches())
+}
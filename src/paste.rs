@@ -0,0 +1,80 @@
+/// How pasted tokens are matched against the options
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PasteMatch {
+    /// First option accepted by the state's filter (or search key)
+    Filter,
+    /// Option whose display label equals the token (ignoring case)
+    Exact,
+}
+
+impl Default for PasteMatch {
+    fn default() -> Self {
+        PasteMatch::Exact
+    }
+}
+
+/// Split pasted text into trimmed, non-empty tokens. Delimiters inside double
+/// quotes (e.g. `"Smith, John"` from a spreadsheet) don't split, and the quotes
+/// are dropped.
+pub(crate) fn split_tokens(text: &str, delimiters: &[char]) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && delimiters.contains(&c) {
+            push_token(&mut tokens, &token);
+            token.clear();
+        } else {
+            token.push(c);
+        }
+    }
+    push_token(&mut tokens, &token);
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<String>, token: &str) {
+    let token = token.trim();
+    if !token.is_empty() {
+        tokens.push(token.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_text_has_no_tokens() {
+        assert!(split_tokens("", &[',']).is_empty());
+        assert!(split_tokens(" \n ", &[',', '\n']).is_empty());
+    }
+
+    #[test]
+    fn blank_tokens_are_dropped() {
+        assert_eq!(split_tokens(",a, ,\n b,,", &[',', '\n']), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn duplicate_tokens_are_kept() {
+        // Matching them to options (and skipping repeats) is up to the select
+        assert_eq!(split_tokens("a,b,a", &[',']), vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn text_without_delimiters_is_one_token() {
+        assert_eq!(split_tokens(" a b ", &[',']), vec!["a b"]);
+        assert_eq!(split_tokens("a,b", &[]), vec!["a,b"]);
+    }
+
+    #[test]
+    fn quoted_delimiters_do_not_split() {
+        assert_eq!(
+            split_tokens("\"Smith, John\", Doe\n\" \"", &[',', '\n']),
+            vec!["Smith, John", "Doe"]
+        );
+        // An unclosed quote runs to the end
+        assert_eq!(split_tokens("a, \"b, c", &[',']), vec!["a", "b, c"]);
+    }
+}
@@ -0,0 +1,50 @@
+//! Menu placement and scrolling, measured against the window or a `scroll_parent`
+
+use web_sys::Element;
+
+/// Events after which the menu is placed again: scrolling the page or any
+/// container (in the capture phase, since `scroll` doesn't bubble), or resizing
+pub(crate) const VIEWPORT_EVENTS: &[(&str, bool)] = &[("scroll", true), ("resize", false)];
+
+/// Top and bottom (in viewport coordinates) of the area the menu has to fit in
+fn bounds(scroll_parent: Option<&Element>) -> (f64, f64) {
+    match scroll_parent {
+        Some(parent) => {
+            let rect = parent.get_bounding_client_rect();
+            (rect.top(), rect.bottom())
+        }
+        None => {
+            let height = web_sys::window()
+                .and_then(|window| window.inner_height().ok())
+                .and_then(|height| height.as_f64())
+                .unwrap_or(0.0);
+            (0.0, height)
+        }
+    }
+}
+
+/// Whether the menu should open above `dropdown`: it doesn't fit below, and
+/// there is more room above.
+pub(crate) fn drop_up(dropdown: &Element, menu: &Element, scroll_parent: Option<&Element>) -> bool {
+    let (top, bottom) = bounds(scroll_parent);
+    let trigger = dropdown.get_bounding_client_rect();
+    let above = trigger.top() - top;
+    let below = bottom - trigger.bottom();
+    f64::from(menu.scroll_height()) > below && above > below
+}
+
+/// Scroll `container` by the least amount showing all of `element` (like
+/// `scrollIntoView` with `block: "nearest"`, but without touching the other
+/// scrolling ancestors, e.g. the page behind a modal)
+pub(crate) fn scroll_into(container: &Element, element: &Element) {
+    let outer = container.get_bounding_client_rect();
+    let inner = element.get_bounding_client_rect();
+    let offset = if inner.top() < outer.top() {
+        inner.top() - outer.top()
+    } else if inner.bottom() > outer.bottom() {
+        (inner.bottom() - outer.bottom()).min(inner.top() - outer.top())
+    } else {
+        return;
+    };
+    container.set_scroll_top(container.scroll_top() + offset.round() as i32);
+}
@@ -0,0 +1,363 @@
+//! Interactive playground for the select (enabled with the `dev-harness` feature).
+
+use yew::prelude::*;
+
+use crate::{Select, SelectDisplay, SelectFilter, SelectState, Selection, Theme};
+
+/// Selection mode knob
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Single, always one selected
+    Required,
+    /// Single, may be empty
+    Optional,
+    Multiple,
+}
+
+impl Mode {
+    const ALL: [Mode; 3] = [Mode::Required, Mode::Optional, Mode::Multiple];
+
+    fn label(self) -> &'static str {
+        match self {
+            Mode::Required => "Single",
+            Mode::Optional => "Nullable",
+            Mode::Multiple => "Multiple",
+        }
+    }
+
+    fn selection(self) -> Selection {
+        match self {
+            Mode::Required => Selection::one(0),
+            Mode::Optional => Selection::none(),
+            Mode::Multiple => Selection::empty(),
+        }
+    }
+}
+
+/// Bulma control size knob
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Size {
+    Small,
+    Normal,
+    Medium,
+    Large,
+}
+
+impl Size {
+    const ALL: [Size; 4] = [Size::Small, Size::Normal, Size::Medium, Size::Large];
+
+    fn label(self) -> &'static str {
+        match self {
+            Size::Small => "Small",
+            Size::Normal => "Normal",
+            Size::Medium => "Medium",
+            Size::Large => "Large",
+        }
+    }
+
+    fn class(self) -> Option<&'static str> {
+        match self {
+            Size::Small => Some("is-small"),
+            Size::Normal => None,
+            Size::Medium => Some("is-medium"),
+            Size::Large => Some("is-large"),
+        }
+    }
+
+    /// The Bulma theme, with the inputs and tags at this size
+    fn theme(self) -> Theme {
+        let bulma = Theme::bulma();
+        Theme {
+            input: classes!(bulma.input.clone(), self.class()),
+            tag: classes!(bulma.tag.clone(), self.class()),
+            ..bulma
+        }
+    }
+}
+
+/// Boolean prop knobs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flag {
+    OmitSelected,
+    Checkboxes,
+    SelectedFirst,
+    DidYouMean,
+    Loading,
+    Readonly,
+    Disabled,
+}
+
+impl Flag {
+    const ALL: [Flag; 7] = [
+        Flag::OmitSelected,
+        Flag::Checkboxes,
+        Flag::SelectedFirst,
+        Flag::DidYouMean,
+        Flag::Loading,
+        Flag::Readonly,
+        Flag::Disabled,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Flag::OmitSelected => "omit_selected",
+            Flag::Checkboxes => "checkboxes",
+            Flag::SelectedFirst => "selected_first",
+            Flag::DidYouMean => "did_you_mean",
+            Flag::Loading => "loading",
+            Flag::Readonly => "readonly",
+            Flag::Disabled => "disabled",
+        }
+    }
+}
+
+/// Option counts offered by the knob
+const COUNTS: [usize; 4] = [5, 100, 1_000, 10_000];
+/// Limits offered by the `max_results` and `max_selected` knobs
+const LIMITS: [Option<usize>; 4] = [None, Some(3), Some(10), Some(50)];
+/// Events kept in the log
+const LOG_LIMIT: usize = 20;
+
+/// A [`Select`] over sample options, with knobs for its props and a log of
+/// the events it emits, to exercise prop combinations while developing.
+pub struct SelectPlayground {
+    link: ComponentLink<Self>,
+    props: SelectPlaygroundProps,
+
+    mode: Mode,
+    size: Size,
+    count: usize,
+    flags: Vec<Flag>,
+    max_results: Option<usize>,
+    max_selected: Option<usize>,
+
+    display: SelectDisplay<String>,
+    state: SelectState<String>,
+    /// Emitted events, most recent first
+    log: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct SelectPlaygroundProps {
+    /// Options to play with (numbered sample options if empty)
+    #[prop_or_default]
+    pub options: Vec<String>,
+}
+
+pub enum Msg {
+    SetMode(Mode),
+    SetSize(Size),
+    SetCount(usize),
+    Toggle(Flag),
+    SetMaxResults(Option<usize>),
+    SetMaxSelected(Option<usize>),
+
+    Selected(usize),
+    Removed(usize),
+    Search(String),
+    Discarded(String),
+}
+
+impl SelectPlayground {
+    fn build_state(props: &SelectPlaygroundProps, mode: Mode, count: usize) -> SelectState<String> {
+        let options = if props.options.is_empty() {
+            (0..count).map(|i| format!("Option {}", i)).collect()
+        } else {
+            props.options.clone()
+        };
+        SelectState::new(
+            options,
+            mode.selection(),
+            SelectFilter::new(|item: &String, search: &str| {
+                item.to_lowercase().contains(&search.to_lowercase())
+            }),
+        )
+    }
+
+    fn rebuild_state(&mut self) {
+        self.state = Self::build_state(&self.props, self.mode, self.count);
+    }
+
+    fn is_set(&self, flag: Flag) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    fn log(&mut self, event: String) {
+        self.log.insert(0, event);
+        self.log.truncate(LOG_LIMIT);
+    }
+
+    fn limit_label(limit: Option<usize>) -> String {
+        limit.map_or_else(|| String::from("None"), |limit| limit.to_string())
+    }
+
+    /// A row of buttons picking one of `choices`
+    fn view_choices<C: Copy + PartialEq + 'static>(
+        &self,
+        label: &str,
+        choices: &[C],
+        current: C,
+        text: impl Fn(C) -> String,
+        msg: impl Fn(C) -> Msg + Copy + 'static,
+    ) -> Html {
+        html! {
+            <div class="field">
+                <label class="label is-small">{ label }</label>
+                <div class="buttons has-addons">
+                {
+                    for choices.iter().map(|&choice| html! {
+                        <button
+                            class=classes!("button", "is-small", if choice == current { Some("is-link") } else { None })
+                            onclick=self.link.callback(move |_| msg(choice))
+                        >
+                            { text(choice) }
+                        </button>
+                    })
+                }
+                </div>
+            </div>
+        }
+    }
+
+    fn view_knobs(&self) -> Html {
+        html! {
+            <>
+                { self.view_choices("Mode", &Mode::ALL, self.mode, |mode| mode.label().to_string(), Msg::SetMode) }
+                { self.view_choices("Size", &Size::ALL, self.size, |size| size.label().to_string(), Msg::SetSize) }
+                {
+                    if self.props.options.is_empty() {
+                        self.view_choices("Options", &COUNTS, self.count, |count| count.to_string(), Msg::SetCount)
+                    } else {
+                        html! {}
+                    }
+                }
+                { self.view_choices("max_results", &LIMITS, self.max_results, Self::limit_label, Msg::SetMaxResults) }
+                { self.view_choices("max_selected", &LIMITS, self.max_selected, Self::limit_label, Msg::SetMaxSelected) }
+                <div class="field">
+                {
+                    for Flag::ALL.iter().map(|&flag| html! {
+                        <label class="checkbox mr-3">
+                            <input
+                                type="checkbox"
+                                class="mr-1"
+                                checked=self.is_set(flag)
+                                onclick=self.link.callback(move |_| Msg::Toggle(flag))
+                            />
+                            <code>{ flag.label() }</code>
+                        </label>
+                    })
+                }
+                </div>
+            </>
+        }
+    }
+}
+
+impl Component for SelectPlayground {
+    type Message = Msg;
+    type Properties = SelectPlaygroundProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mode = Mode::Optional;
+        let count = COUNTS[1];
+        Self {
+            link,
+            state: Self::build_state(&props, mode, count),
+            props,
+
+            mode,
+            size: Size::Normal,
+            count,
+            flags: Vec::new(),
+            max_results: None,
+            max_selected: None,
+
+            display: SelectDisplay::new(|item: &String| item.clone()),
+            log: Vec::new(),
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            self.props = props;
+            self.rebuild_state();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetMode(mode) => {
+                self.mode = mode;
+                self.rebuild_state();
+            }
+            Msg::SetSize(size) => self.size = size,
+            Msg::SetCount(count) => {
+                self.count = count;
+                self.rebuild_state();
+            }
+            Msg::Toggle(flag) => {
+                if self.is_set(flag) {
+                    self.flags.retain(|set| *set != flag);
+                } else {
+                    self.flags.push(flag);
+                }
+            }
+            Msg::SetMaxResults(max_results) => self.max_results = max_results,
+            Msg::SetMaxSelected(max_selected) => self.max_selected = max_selected,
+
+            Msg::Selected(index) => {
+                self.state.select(index);
+                self.log(format!("onselected({})", index));
+            }
+            Msg::Removed(index) => {
+                self.state.deselect(index);
+                self.log(format!("onremoved({})", index));
+            }
+            Msg::Search(query) => self.log(format!("onsearch({:?})", query)),
+            Msg::Discarded(query) => self.log(format!("ondiscard({:?})", query)),
+        }
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="columns">
+                <div class="column is-two-fifths">
+                    { self.view_knobs() }
+                </div>
+                <div class="column">
+                    <Select<String>
+                        state=self.state.clone()
+                        display=self.display.clone()
+                        theme=self.size.theme()
+                        omit_selected=self.is_set(Flag::OmitSelected)
+                        checkboxes=self.is_set(Flag::Checkboxes)
+                        selected_first=self.is_set(Flag::SelectedFirst)
+                        did_you_mean=self.is_set(Flag::DidYouMean)
+                        loading=self.is_set(Flag::Loading)
+                        readonly=self.is_set(Flag::Readonly)
+                        disabled=self.is_set(Flag::Disabled)
+                        max_results=self.max_results
+                        max_selected=self.max_selected
+                        onselected=self.link.callback(Msg::Selected)
+                        onremoved=self.link.callback(Msg::Removed)
+                        onsearch=self.link.callback(Msg::Search)
+                        ondiscard=self.link.callback(Msg::Discarded)
+                    />
+                    <pre class="mt-4">
+                    {
+                        if self.log.is_empty() {
+                            String::from("No events yet")
+                        } else {
+                            self.log.join("\n")
+                        }
+                    }
+                    </pre>
+                </div>
+            </div>
+        }
+    }
+}
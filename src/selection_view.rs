@@ -0,0 +1,119 @@
+use yew::prelude::*;
+
+use crate::{SelectDisplay, SelectState, Theme};
+
+/// Read-only display of the current selection of a shared [`SelectState`]
+/// (tags in multiple mode, text otherwise), e.g. for summary panes that must
+/// stay in sync with an editable [`Select`](crate::Select).
+pub struct SelectionView<T: 'static> {
+    link: ComponentLink<Self>,
+    props: SelectionViewProps<T>,
+    /// State generation at the last render, to pick up changes made through shared handles
+    generation: usize,
+    /// Id of the state subscription, to re-render on changes made elsewhere
+    subscription: usize,
+}
+
+#[derive(Properties)]
+pub struct SelectionViewProps<T: 'static> {
+    pub state: SelectState<T>,
+    pub display: SelectDisplay<T>,
+
+    #[prop_or_default]
+    pub theme: Theme,
+    /// Text shown when nothing is selected
+    #[prop_or_default]
+    pub empty: String,
+}
+
+// This SHOULD be the auto impl, but for some reason that thinks that T needs to be Clone
+impl<T> Clone for SelectionViewProps<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            display: self.display.clone(),
+            theme: self.theme.clone(),
+            empty: self.empty.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for SelectionViewProps<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+            && self.display == other.display
+            && self.theme == other.theme
+            && self.empty == other.empty
+    }
+}
+
+impl<T: 'static> Component for SelectionView<T> {
+    type Properties = SelectionViewProps<T>;
+    type Message = ();
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            generation: props.state.generation(),
+            subscription: Self::subscribe(&link, &props.state),
+            link,
+            props,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            if self.props.state != props.state {
+                self.props.state.unsubscribe(self.subscription);
+                self.subscription = Self::subscribe(&self.link, &props.state);
+            }
+            self.props = props;
+            true
+        } else {
+            self.generation != self.props.state.generation()
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        self.generation != self.props.state.generation()
+    }
+
+    fn view(&self) -> Html {
+        let selected = self.props.state.selected_items();
+        if selected.is_empty() {
+            html! { <span class="has-text-grey">{ &self.props.empty }</span> }
+        } else if self.props.state.is_multiple() {
+            html! {
+                <div class="tags">
+                {
+                    for selected.into_iter().map(|(i, item)| html! {
+                        <span key=i.to_string() class=self.props.theme.tag.clone()>
+                            { self.props.display.call(item) }
+                        </span>
+                    })
+                }
+                </div>
+            }
+        } else {
+            html! {
+                <span>
+                    { for selected.into_iter().map(|(_, item)| self.props.display.call(item)) }
+                </span>
+            }
+        }
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        self.generation = self.props.state.generation();
+    }
+
+    fn destroy(&mut self) {
+        self.props.state.unsubscribe(self.subscription);
+    }
+}
+
+impl<T> SelectionView<T> {
+    fn subscribe(link: &ComponentLink<Self>, state: &SelectState<T>) -> usize {
+        let link = link.clone();
+        state.subscribe(move || link.send_message(()))
+    }
+}
@@ -0,0 +1,106 @@
+//! "Did you mean" suggestions for queries without any matches.
+
+/// Labels close to `query` by edit distance (to the whole label, or to one of
+/// its words), closest first
+pub(crate) fn suggestions(labels: &[String], query: &str, limit: usize) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    let query_len = query.chars().count();
+    if query_len < 3 {
+        return Vec::new();
+    }
+    // Allow roughly one typo per three characters
+    let max_distance = query_len / 3;
+
+    let mut scored = labels
+        .iter()
+        .filter_map(|label| {
+            let lower = label.to_lowercase();
+            std::iter::once(lower.as_str())
+                .chain(lower.split_whitespace())
+                .map(|candidate| distance(&query, candidate))
+                .min()
+                .filter(|&d| d <= max_distance)
+                .map(|d| (d, label))
+        })
+        .collect::<Vec<_>>();
+    // Stable, so equally close labels keep the option order
+    scored.sort_by_key(|&(d, _)| d);
+
+    let mut found: Vec<String> = Vec::with_capacity(limit);
+    for (_, label) in scored {
+        if found.len() == limit {
+            break;
+        }
+        if !found.contains(label) {
+            found.push(label.clone());
+        }
+    }
+    found
+}
+
+/// Levenshtein distance between two strings (by chars)
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn short_or_empty_queries_get_no_suggestions() {
+        let labels = labels(&["Apple", "Banana"]);
+        assert!(suggestions(&labels, "", 3).is_empty());
+        assert!(suggestions(&labels, "  ", 3).is_empty());
+        assert!(suggestions(&labels, "ap", 3).is_empty());
+        assert!(suggestions(&[], "apple", 3).is_empty());
+    }
+
+    #[test]
+    fn closest_labels_come_first() {
+        let labels = labels(&["Bandana", "Banana", "Cherry"]);
+        assert_eq!(
+            suggestions(&labels, "bananas", 3),
+            vec!["Banana", "Bandana"]
+        );
+        assert_eq!(
+            suggestions(&labels, "BANDANA", 3),
+            vec!["Bandana", "Banana"]
+        );
+    }
+
+    #[test]
+    fn words_of_a_label_are_matched() {
+        let labels = labels(&["Granny Smith", "Golden Delicious"]);
+        assert_eq!(suggestions(&labels, "smitt", 3), vec!["Granny Smith"]);
+    }
+
+    #[test]
+    fn duplicate_labels_are_suggested_once() {
+        let labels = labels(&["Berry", "Cherry", "Cherry"]);
+        assert_eq!(suggestions(&labels, "cherry", 3), vec!["Cherry", "Berry"]);
+        assert_eq!(suggestions(&labels, "cherry", 1), vec!["Cherry"]);
+    }
+
+    #[test]
+    fn distance_counts_edits() {
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("héllo", "hello"), 1);
+    }
+}
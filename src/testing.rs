@@ -0,0 +1,170 @@
+//! Headless helpers for testing code that wires up a [`Select`](crate::Select).
+//!
+//! [`SelectHarness`] runs the shared interaction reducer
+//! ([`core::reduce`](crate::core::reduce), through a [`SelectSession`]) against
+//! a [`SelectState`] without needing a browser, so selection wiring can be
+//! checked with a plain `cargo test`.
+//!
+//! It does **not** mount the [`Select`](crate::Select) component itself. The
+//! component runs its highlight changes (arrow keys, Home/End, the pointer and
+//! the row highlighted on opening) through the same reducer, but behaviour that
+//! only lives in its `update` (tags, groups, ranges, footer fields, actions,
+//! creating, autofill, `onchange`, scrolling) isn't covered; test that in a
+//! browser, as `tests/interaction.rs` does.
+
+use crate::{
+    core::{Effect, Input},
+    HighlightSource, SelectSession, SelectState,
+};
+
+pub use crate::core::Key;
+
+/// Callbacks that the component would have emitted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectEvent {
+    /// `onselected` was emitted with this index
+    Selected(usize),
+    /// `onremoved` was emitted with this index
+    Removed(usize),
+}
+
+/// Drives the select's reducer headlessly and records emitted events.
+///
+/// By default, emitted events are applied to the state the same way the
+/// typical `onselected`/`onremoved` wiring does (`select`/`deselect`). Use
+/// [`SelectHarness::manual`] to only record them.
+pub struct SelectHarness<T: 'static> {
+    session: SelectSession<T>,
+    apply_events: bool,
+
+    events: Vec<SelectEvent>,
+}
+
+impl<T> SelectHarness<T> {
+    pub fn new(state: SelectState<T>) -> Self {
+        Self {
+            session: SelectSession::new(state),
+            apply_events: true,
+
+            events: Vec::new(),
+        }
+    }
+
+    /// Create a harness that records events without applying them to the state
+    pub fn manual(state: SelectState<T>) -> Self {
+        Self {
+            apply_events: false,
+            ..Self::new(state)
+        }
+    }
+
+    /// Mirror the `omit_selected` prop
+    pub fn omit_selected(mut self, omit_selected: bool) -> Self {
+        self.session = self.session.omit_selected(omit_selected);
+        self
+    }
+
+    /// Mirror the `enter_commits` prop
+    pub fn enter_commits(mut self, enter_commits: HighlightSource) -> Self {
+        self.session = self.session.enter_commits(enter_commits);
+        self
+    }
+
+    pub fn state(&self) -> &SelectState<T> {
+        self.session.state()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.session.interaction().open
+    }
+
+    pub fn query(&self) -> &str {
+        &self.session.interaction().query
+    }
+
+    /// Position of the highlighted item in the (visible) filtered list, which
+    /// Enter commits (the hovered one only with `HighlightSource::Pointer`)
+    pub fn highlighted(&self) -> usize {
+        self.session.highlighted()
+    }
+
+    /// Events emitted so far
+    pub fn events(&self) -> &[SelectEvent] {
+        &self.events
+    }
+
+    /// Take the events emitted so far, clearing the log
+    pub fn take_events(&mut self) -> Vec<SelectEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Global indices of the items that would be shown in the dropdown
+    pub fn visible(&self) -> Vec<usize> {
+        self.session.visible()
+    }
+
+    /// Global indices of the currently selected items
+    pub fn selected(&self) -> Vec<usize> {
+        self.state()
+            .selected_items()
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn focus(&mut self) {
+        self.dispatch(Input::Focus);
+    }
+
+    pub fn blur(&mut self) {
+        self.dispatch(Input::Blur);
+    }
+
+    /// Replace the search text, as if typed into the input
+    pub fn input(&mut self, text: &str) {
+        self.dispatch(Input::Query(text.to_string()));
+    }
+
+    pub fn clear_search(&mut self) {
+        self.session.clear_search();
+    }
+
+    pub fn press(&mut self, key: Key) {
+        self.dispatch(Input::Key(key));
+    }
+
+    /// Move the pointer over the item at `position` in the visible list
+    pub fn hover(&mut self, position: usize) {
+        self.dispatch(Input::Hover(position));
+    }
+
+    /// Move the pointer out of the dropdown
+    pub fn unhover(&mut self) {
+        self.dispatch(Input::Unhover);
+    }
+
+    /// Click the item at `position` in the visible list
+    pub fn click(&mut self, position: usize) {
+        self.dispatch(Input::Pick(position));
+    }
+
+    /// Click the delete button on the tag for the item at global `index`
+    pub fn remove(&mut self, index: usize) {
+        self.events.push(SelectEvent::Removed(index));
+        if self.apply_events {
+            self.session.state().deselect(index);
+        }
+    }
+
+    /// Run `input` through the session and carry out its effects
+    fn dispatch(&mut self, input: Input) {
+        for effect in self.session.dispatch(input) {
+            if let Effect::Select(index) = effect {
+                self.events.push(SelectEvent::Selected(index));
+                if self.apply_events {
+                    self.session.state().select(index);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use yew::{classes, Classes};
+
+/// Class lists applied to each logical part of the select.
+///
+/// Defaults to Bulma's dropdown classes, but every part can be replaced so the
+/// component can be restyled (e.g. for Tailwind) without touching the view code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Outer dropdown wrapper
+    pub dropdown: Classes,
+    /// Added to the outer wrapper while the menu is open
+    pub open: Classes,
+    /// Focus ring hook, added to the trigger and the highlighted row while
+    /// using the keyboard (e.g. after tabbing into the input), and to the tag
+    /// focused with the arrow keys (Bulma has no focus styling for these)
+    pub focus_visible: Classes,
+    /// Added to the outer wrapper when the menu opens above the input (it
+    /// doesn't fit below, see `scroll_parent`)
+    pub up: Classes,
+    /// Wrapper around the input field(s)
+    pub trigger: Classes,
+    /// Text inputs
+    pub input: Classes,
+    /// Added to the inputs while `loading` is set
+    pub loading: Classes,
+    /// Dropdown menu container
+    pub menu: Classes,
+    /// Inner content of the dropdown menu
+    pub content: Classes,
+    /// Each option row in the dropdown
+    pub item: Classes,
+    /// Added to the option row highlighted by the keyboard/mouse
+    pub active_item: Classes,
+    /// Added to option rows that are currently selected
+    pub selected_item: Classes,
+    /// Added to an option while it is pressed (for a long-press toggle on touch devices)
+    pub pressed_item: Classes,
+    /// Added to option rows disabled through `SelectState::set_meta`
+    pub disabled_item: Classes,
+    /// Tag at the end of an option row (e.g. from `OptionMeta::badge`)
+    pub badge: Classes,
+    /// Button at the end of an option row (with `onoptionaction`)
+    pub item_action: Classes,
+    /// Group headers (when options are grouped)
+    pub group: Classes,
+    /// Chevron in a group header collapsing or expanding it (with `collapsible_groups`)
+    pub group_toggle: Classes,
+    /// Divider rows between options (and above the footer)
+    pub divider: Classes,
+    /// Panel beside the dropdown with details of the highlighted option (with `preview`)
+    pub preview: Classes,
+    /// Notice at the top of the dropdown (e.g. when `max_selected` blocks a pick)
+    pub notice: Classes,
+    /// Row of recent search queries (with `search_history`)
+    pub history: Classes,
+    /// Each recent search query
+    pub history_chip: Classes,
+    /// Wrapper of the image shown beside each option (with `avatar`)
+    pub avatar: Classes,
+    /// Selected item tags (in multiple mode)
+    pub tag: Classes,
+    /// Added to the tag focused with the arrow keys
+    pub active_tag: Classes,
+    /// Tags for selections which no longer point at an option
+    pub invalid_tag: Classes,
+    /// Added to the input (in single mode) when the selection no longer points at an option
+    pub invalid_input: Classes,
+    /// Validation error message under the input
+    pub error: Classes,
+    /// Added to the input while there is a validation error
+    pub error_input: Classes,
+    /// Wrapper of the image shown inside each tag (with `avatar`)
+    pub tag_avatar: Classes,
+    /// The delete button used to clear the search text
+    pub delete: Classes,
+    /// The delete button inside each tag (a `<button>`, so it can be reached with Tab)
+    pub tag_delete: Classes,
+    /// Button beside the input opening the bulk entry dialog (with `bulk_entry`)
+    pub bulk_button: Classes,
+    /// Outer element of the bulk entry dialog
+    pub bulk_dialog: Classes,
+    /// Text area of the bulk entry dialog
+    pub bulk_textarea: Classes,
+    /// Pasted values matching an option, listed in the bulk entry dialog
+    pub bulk_matched: Classes,
+    /// Pasted values without a matching option, listed in the bulk entry dialog
+    pub bulk_unmatched: Classes,
+    /// Button selecting the matched values of the bulk entry dialog
+    pub bulk_apply: Classes,
+    /// Button closing the bulk entry dialog
+    pub bulk_cancel: Classes,
+}
+
+impl Theme {
+    /// The default Bulma theme
+    pub fn bulma() -> Self {
+        Self {
+            dropdown: classes!("dropdown"),
+            open: classes!("is-active"),
+            up: classes!("is-up"),
+            focus_visible: classes!("ybss-focus-visible"),
+            trigger: classes!("dropdown-trigger"),
+            input: classes!("input"),
+            loading: classes!("is-loading"),
+            menu: classes!("dropdown-menu"),
+            content: classes!("dropdown-content"),
+            item: classes!("dropdown-item"),
+            active_item: classes!("is-active"),
+            selected_item: classes!("has-background-primary-light"),
+            pressed_item: classes!("has-background-grey-lighter"),
+            disabled_item: classes!("has-text-grey-light"),
+            badge: classes!("tag", "is-rounded", "is-light", "is-pulled-right", "ml-2"),
+            item_action: classes!("button", "is-small", "is-white", "is-pulled-right", "ml-2"),
+            group: classes!("dropdown-item", "has-text-weight-semibold"),
+            group_toggle: classes!("button", "is-small", "is-white", "is-pulled-right", "ml-2"),
+            divider: classes!("dropdown-divider"),
+            preview: classes!("box", "ml-2"),
+            notice: classes!("dropdown-item", "has-text-danger"),
+            history: classes!("dropdown-item", "tags"),
+            history_chip: classes!("tag", "is-rounded", "is-clickable"),
+            avatar: classes!("image", "is-24x24", "is-inline-block", "mr-2"),
+            tag: classes!("tag"),
+            active_tag: classes!("is-dark"),
+            invalid_tag: classes!("tag", "is-warning"),
+            invalid_input: classes!("is-warning"),
+            error: classes!("help", "is-danger"),
+            error_input: classes!("is-danger"),
+            tag_avatar: classes!("image", "is-16x16", "mr-1"),
+            delete: classes!("delete"),
+            tag_delete: classes!("delete", "is-small"),
+            bulk_button: classes!("button", "is-small", "is-white"),
+            bulk_dialog: classes!("modal", "is-active"),
+            bulk_textarea: classes!("textarea"),
+            bulk_matched: classes!("tag", "is-success", "is-light"),
+            bulk_unmatched: classes!("tag", "is-warning", "is-light"),
+            bulk_apply: classes!("button", "is-primary"),
+            bulk_cancel: classes!("button"),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::bulma()
+    }
+}
@@ -0,0 +1,20 @@
+//! One-off timers (e.g. to hide the menu after its leave transition)
+
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Call `callback` once after `ms` milliseconds (or right away if there is no
+/// window). The timer can't be cancelled, so stale calls should be ignored by
+/// the caller (e.g. by passing a generation).
+pub(crate) fn set_timeout(ms: u32, callback: impl FnOnce() + 'static) {
+    match web_sys::window() {
+        Some(window) => {
+            // Freed by wasm-bindgen once it has been called
+            let function = Closure::once_into_js(callback);
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                function.unchecked_ref(),
+                ms as i32,
+            );
+        }
+        None => callback(),
+    }
+}
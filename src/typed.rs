@@ -0,0 +1,246 @@
+use yew::prelude::*;
+
+use crate::{Select, SelectDisplay, SelectState, SelectValue};
+
+/// A [`Select`] whose callbacks emit a value extracted from the options (e.g. a
+/// database ID) instead of an option index.
+///
+/// Unlike a plain `Select`, it applies the picks and removals to the state
+/// itself (the indices would be of no use to the callbacks), so the callbacks
+/// only report them.
+pub struct TypedSelect<T: 'static, V: 'static> {
+    props: TypedSelectProps<T, V>,
+
+    onselected: Callback<usize>,
+    onremoved: Callback<usize>,
+    onselectedmany: Callback<Vec<usize>>,
+    onremovedmany: Callback<Vec<usize>>,
+}
+
+#[derive(Properties)]
+pub struct TypedSelectProps<T: 'static, V: 'static> {
+    pub state: SelectState<T>,
+    pub display: SelectDisplay<T>,
+    /// Extracts the emitted value from an option
+    pub value_of: SelectValue<T, V>,
+
+    #[prop_or_default]
+    pub onselected: Option<Callback<V>>,
+    #[prop_or_default]
+    pub onremoved: Option<Callback<V>>,
+    #[prop_or_default]
+    pub onselectedmany: Option<Callback<Vec<V>>>,
+    #[prop_or_default]
+    pub onremovedmany: Option<Callback<Vec<V>>>,
+
+    #[prop_or_default]
+    pub omit_selected: bool,
+    #[prop_or_else(|| String::from("Type to search"))]
+    pub placeholder: String,
+    #[prop_or_default]
+    pub readonly: bool,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub loading: bool,
+}
+
+// This SHOULD be the auto impl, but for some reason that thinks that T and V need to be Clone
+impl<T, V> Clone for TypedSelectProps<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            display: self.display.clone(),
+            value_of: self.value_of.clone(),
+
+            onselected: self.onselected.clone(),
+            onremoved: self.onremoved.clone(),
+            onselectedmany: self.onselectedmany.clone(),
+            onremovedmany: self.onremovedmany.clone(),
+
+            omit_selected: self.omit_selected,
+            placeholder: self.placeholder.clone(),
+            readonly: self.readonly,
+            disabled: self.disabled,
+            loading: self.loading,
+        }
+    }
+}
+
+impl<T, V> PartialEq for TypedSelectProps<T, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+            && self.display == other.display
+            && self.value_of == other.value_of
+            && self.onselected == other.onselected
+            && self.onremoved == other.onremoved
+            && self.onselectedmany == other.onselectedmany
+            && self.onremovedmany == other.onremovedmany
+            && self.omit_selected == other.omit_selected
+            && self.placeholder == other.placeholder
+            && self.readonly == other.readonly
+            && self.disabled == other.disabled
+            && self.loading == other.loading
+    }
+}
+
+/// Callback applying a pick or removal to `state` with `apply`, then emitting the
+/// option's value to `callback`
+fn map_callback<T: 'static, V: 'static>(
+    state: &SelectState<T>,
+    value_of: &SelectValue<T, V>,
+    apply: fn(&SelectState<T>, usize) -> bool,
+    callback: &Option<Callback<V>>,
+) -> Callback<usize> {
+    let state = state.clone();
+    let value_of = value_of.clone();
+    let callback = callback.clone();
+    Callback::from(move |index| {
+        apply(&state, index);
+        if let (Some(callback), Some(item)) = (&callback, state.get(index)) {
+            callback.emit(value_of.call(item));
+        }
+    })
+}
+
+/// Like `map_callback`, for several options at once
+fn map_many_callback<T: 'static, V: 'static>(
+    state: &SelectState<T>,
+    value_of: &SelectValue<T, V>,
+    apply: fn(&SelectState<T>, &[usize]) -> bool,
+    callback: &Option<Callback<Vec<V>>>,
+) -> Callback<Vec<usize>> {
+    let state = state.clone();
+    let value_of = value_of.clone();
+    let callback = callback.clone();
+    Callback::from(move |indices: Vec<usize>| {
+        apply(&state, &indices);
+        if let Some(ref callback) = callback {
+            callback.emit(
+                indices
+                    .into_iter()
+                    .filter_map(|index| state.get(index).map(|item| value_of.call(item)))
+                    .collect(),
+            );
+        }
+    })
+}
+
+impl<T: 'static, V: 'static> TypedSelect<T, V> {
+    fn new(props: TypedSelectProps<T, V>) -> Self {
+        let (state, value_of) = (&props.state, &props.value_of);
+        Self {
+            onselected: map_callback(state, value_of, SelectState::select, &props.onselected),
+            onremoved: map_callback(state, value_of, SelectState::deselect, &props.onremoved),
+            onselectedmany: map_many_callback(
+                state,
+                value_of,
+                SelectState::select_many,
+                &props.onselectedmany,
+            ),
+            onremovedmany: map_many_callback(
+                state,
+                value_of,
+                SelectState::deselect_many,
+                &props.onremovedmany,
+            ),
+            props,
+        }
+    }
+}
+
+impl<T: 'static, V: 'static> Component for TypedSelect<T, V> {
+    type Properties = TypedSelectProps<T, V>;
+    type Message = ();
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self::new(props)
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            *self = Self::new(props);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <Select<T>
+                state=self.props.state.clone()
+                display=self.props.display.clone()
+                onselected=self.onselected.clone()
+                onremoved=self.onremoved.clone()
+                onselectedmany=self.onselectedmany.clone()
+                onremovedmany=self.onremovedmany.clone()
+                omit_selected=self.props.omit_selected
+                placeholder=self.props.placeholder.clone()
+                readonly=self.props.readonly
+                disabled=self.props.disabled
+                loading=self.props.loading
+            />
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{SelectFilter, Selection};
+
+    type Row = (u32, &'static str);
+
+    fn state() -> SelectState<Row> {
+        SelectState::new(
+            vec![(10, "a"), (20, "b"), (30, "c")],
+            Selection::empty(),
+            SelectFilter::new(|row: &Row, search: &str| row.1.contains(search)),
+        )
+    }
+
+    #[test]
+    fn callbacks_apply_the_change_and_emit_values() {
+        let state = state();
+        let value_of = SelectValue::new(|row: &Row| row.0);
+        let emitted = Rc::new(RefCell::new(Vec::new()));
+        let record = Some(Callback::from({
+            let emitted = emitted.clone();
+            move |id: u32| emitted.borrow_mut().push(id)
+        }));
+
+        map_callback(&state, &value_of, SelectState::select, &record).emit(1);
+        assert!(state.is_selected(1));
+        map_callback(&state, &value_of, SelectState::deselect, &record).emit(1);
+        assert!(!state.is_selected(1));
+        assert_eq!(*emitted.borrow(), vec![20, 20]);
+
+        // Applied even without a callback to report to
+        map_callback(&state, &value_of, SelectState::select, &None).emit(0);
+        assert!(state.is_selected(0));
+    }
+
+    #[test]
+    fn many_callbacks_apply_the_change_and_emit_values() {
+        let state = state();
+        let value_of = SelectValue::new(|row: &Row| row.0);
+        let emitted = Rc::new(RefCell::new(Vec::new()));
+        let record = Some(Callback::from({
+            let emitted = emitted.clone();
+            move |ids: Vec<u32>| emitted.borrow_mut().push(ids)
+        }));
+
+        map_many_callback(&state, &value_of, SelectState::select_many, &record).emit(vec![0, 2]);
+        assert!(state.is_selected(0) && state.is_selected(2));
+        map_many_callback(&state, &value_of, SelectState::deselect_many, &record).emit(vec![2, 9]);
+        assert!(state.is_selected(0) && !state.is_selected(2));
+        assert_eq!(*emitted.borrow(), vec![vec![10, 30], vec![30]]);
+    }
+}
@@ -1,38 +1,43 @@
 use std::sync::Arc;
 
+use yew::Html;
+
+use crate::EmptyState;
+
 // Use the Box to make sure we're not doing a Arc::ptr_eq on dyn objects (since rust doesn't like that)
-type SelectFilterContainer<T> = Box<dyn Fn(&T, &str) -> bool>;
+type SelectEmptyViewContainer = Box<dyn Fn(&EmptyState) -> Html>;
 
-pub struct SelectFilter<T> {
-    inner: Arc<SelectFilterContainer<T>>,
+/// Renders the dropdown content shown when there are no options to show
+pub struct SelectEmptyView {
+    inner: Arc<SelectEmptyViewContainer>,
 }
 
-impl<T> PartialEq for SelectFilter<T> {
+impl PartialEq for SelectEmptyView {
     fn eq(&self, other: &Self) -> bool {
         Arc::ptr_eq(&self.inner, &other.inner)
     }
 }
 
-impl<T> SelectFilter<T> {
-    pub fn new<F: Fn(&T, &str) -> bool + 'static>(f: F) -> Self {
+impl SelectEmptyView {
+    pub fn new<F: Fn(&EmptyState) -> Html + 'static>(f: F) -> Self {
         Self {
-            inner: Arc::new(Box::new(f) as SelectFilterContainer<T>),
+            inner: Arc::new(Box::new(f) as SelectEmptyViewContainer),
         }
     }
 
     // TODO: impl Fn when traits stabilize?
-    pub fn call(&self, item: &T, input: &str) -> bool {
-        (self.inner)(item, input)
+    pub fn call(&self, state: &EmptyState) -> Html {
+        (self.inner)(state)
     }
 }
 
-impl<T, F: Fn(&T, &str) -> bool + 'static> From<F> for SelectFilter<T> {
+impl<F: Fn(&EmptyState) -> Html + 'static> From<F> for SelectEmptyView {
     fn from(f: F) -> Self {
-        SelectFilter::new(f)
+        SelectEmptyView::new(f)
     }
 }
 
-impl<T> Clone for SelectFilter<T> {
+impl Clone for SelectEmptyView {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
@@ -40,38 +45,39 @@ impl<T> Clone for SelectFilter<T> {
     }
 }
 
-type SelectDisplayContainer<T> = Box<dyn Fn(&T) -> String>;
+type SelectPreviewContainer<T> = Box<dyn Fn(&T) -> Html>;
 
-pub struct SelectDisplay<T> {
-    inner: Arc<SelectDisplayContainer<T>>,
+/// Renders details of an option, in a panel beside the dropdown
+pub struct SelectPreview<T> {
+    inner: Arc<SelectPreviewContainer<T>>,
 }
 
-impl<T> PartialEq for SelectDisplay<T> {
+impl<T> PartialEq for SelectPreview<T> {
     fn eq(&self, other: &Self) -> bool {
         Arc::ptr_eq(&self.inner, &other.inner)
     }
 }
 
-impl<T> SelectDisplay<T> {
-    pub fn new<F: Fn(&T) -> String + 'static>(f: F) -> Self {
+impl<T> SelectPreview<T> {
+    pub fn new<F: Fn(&T) -> Html + 'static>(f: F) -> Self {
         Self {
-            inner: Arc::new(Box::new(f) as SelectDisplayContainer<T>),
+            inner: Arc::new(Box::new(f) as SelectPreviewContainer<T>),
         }
     }
 
     // TODO: impl Fn when traits stabilize?
-    pub fn call(&self, item: &T) -> String {
+    pub fn call(&self, item: &T) -> Html {
         (self.inner)(item)
     }
 }
 
-impl<T, F: Fn(&T) -> String + 'static> From<F> for SelectDisplay<T> {
+impl<T, F: Fn(&T) -> Html + 'static> From<F> for SelectPreview<T> {
     fn from(f: F) -> Self {
-        SelectDisplay::new(f)
+        SelectPreview::new(f)
     }
 }
 
-impl<T> Clone for SelectDisplay<T> {
+impl<T> Clone for SelectPreview<T> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
@@ -0,0 +1,163 @@
+//! Headless tests of the interaction reducer through `SelectHarness`
+
+use yew_bulma_search_select::{
+    fixtures,
+    testing::{Key, SelectEvent, SelectHarness},
+    HighlightSource, Selection,
+};
+
+#[test]
+fn focus_opens_and_blur_closes() {
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::none()));
+    assert!(!select.is_open());
+
+    select.focus();
+    assert!(select.is_open());
+    assert_eq!(select.highlighted(), 0);
+
+    select.blur();
+    assert!(!select.is_open());
+}
+
+#[test]
+fn arrow_keys_move_highlight() {
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::none()));
+
+    // Opens without moving while closed
+    select.press(Key::ArrowDown);
+    assert!(select.is_open());
+    assert_eq!(select.highlighted(), 0);
+
+    select.press(Key::ArrowDown);
+    select.press(Key::ArrowDown);
+    select.press(Key::ArrowDown);
+    assert_eq!(select.highlighted(), 2);
+
+    select.press(Key::ArrowUp);
+    assert_eq!(select.highlighted(), 1);
+
+    select.press(Key::Home);
+    assert_eq!(select.highlighted(), 0);
+    select.press(Key::End);
+    assert_eq!(select.highlighted(), 2);
+}
+
+#[test]
+fn focus_highlights_the_selection() {
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::some(1)));
+    select.focus();
+    assert_eq!(select.highlighted(), 1);
+
+    // Not in multiple mode, where Enter adds to the selection
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::multiple(vec![1])));
+    select.focus();
+    assert_eq!(select.highlighted(), 0);
+}
+
+#[test]
+fn enter_selects_highlighted_and_closes() {
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::none()));
+
+    select.focus();
+    select.press(Key::ArrowDown);
+    select.press(Key::ArrowDown);
+    select.press(Key::Enter);
+
+    assert_eq!(select.events(), &[SelectEvent::Selected(2)]);
+    assert_eq!(select.selected(), vec![2]);
+    assert!(!select.is_open());
+}
+
+#[test]
+fn enter_ignores_hover_by_default() {
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::none()));
+
+    select.focus();
+    select.hover(2);
+    assert_eq!(select.highlighted(), 0);
+    select.press(Key::Enter);
+
+    assert_eq!(select.events(), &[SelectEvent::Selected(0)]);
+}
+
+#[test]
+fn enter_commits_hover_with_pointer() {
+    let mut select = SelectHarness::new(fixtures::state(3, Selection::none()))
+        .enter_commits(HighlightSource::Pointer);
+
+    select.focus();
+    select.hover(2);
+    assert_eq!(select.highlighted(), 2);
+
+    // The arrows move on from the hovered row
+    select.press(Key::ArrowUp);
+    assert_eq!(select.highlighted(), 1);
+
+    select.hover(2);
+    select.unhover();
+    select.press(Key::Enter);
+    assert_eq!(select.events(), &[SelectEvent::Selected(1)]);
+}
+
+#[test]
+fn input_filters_visible() {
+    let mut select = SelectHarness::new(fixtures::state(12, Selection::none()));
+
+    select.focus();
+    select.input("1");
+    assert_eq!(select.query(), "1");
+    assert_eq!(select.visible(), vec![1, 10, 11]);
+
+    // Picks by position in the filtered list
+    select.click(1);
+    assert_eq!(select.take_events(), vec![SelectEvent::Selected(10)]);
+
+    // Closing clears the search
+    assert_eq!(select.query(), "");
+    assert_eq!(select.visible().len(), 12);
+}
+
+#[test]
+fn escape_clears_search() {
+    let mut select = SelectHarness::new(fixtures::state(12, Selection::none()));
+
+    select.focus();
+    select.input("option 3");
+    assert_eq!(select.visible(), vec![3]);
+
+    select.press(Key::Escape);
+    assert!(!select.is_open());
+    assert_eq!(select.query(), "");
+    assert!(select.events().is_empty());
+}
+
+#[test]
+fn omit_selected_hides_selection() {
+    let mut select =
+        SelectHarness::new(fixtures::state(4, Selection::multiple(vec![1]))).omit_selected(true);
+    assert_eq!(select.visible(), vec![0, 2, 3]);
+
+    select.focus();
+    select.click(1);
+    assert_eq!(select.selected(), vec![1, 2]);
+    assert_eq!(select.visible(), vec![0, 3]);
+
+    select.remove(1);
+    assert_eq!(select.selected(), vec![2]);
+    assert_eq!(select.visible(), vec![0, 1, 3]);
+}
+
+#[test]
+fn manual_only_records() {
+    let mut select = SelectHarness::manual(fixtures::state(3, Selection::none()));
+
+    select.focus();
+    select.click(1);
+    select.remove(0);
+
+    assert_eq!(
+        select.events(),
+        &[SelectEvent::Selected(1), SelectEvent::Removed(0)]
+    );
+    assert!(select.selected().is_empty());
+}
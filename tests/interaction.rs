@@ -0,0 +1,119 @@
+//! Browser interaction tests. Run with `wasm-pack test --headless --firefox -- --features testing`
+
+#![cfg(target_arch = "wasm32")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::{Element, HtmlElement, KeyboardEvent, KeyboardEventInit, MouseEvent, MouseEventInit};
+use yew::{App, Callback};
+
+use yew_bulma_search_select::{fixtures, Select, SelectProps, SelectState, Selection};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Mounted {
+    root: Element,
+    selected: Rc<RefCell<Vec<usize>>>,
+}
+
+impl Mounted {
+    fn find(&self, test_id: &str) -> HtmlElement {
+        self.root
+            .query_selector(&format!("[data-testid=\"select-{}\"]", test_id))
+            .unwrap()
+            .unwrap_or_else(|| panic!("missing element {}", test_id))
+            .unchecked_into()
+    }
+
+    fn is_open(&self) -> bool {
+        self.root
+            .first_element_child()
+            .unwrap()
+            .class_list()
+            .contains("is-active")
+    }
+
+    fn key(&self, code: &str) {
+        let mut init = KeyboardEventInit::new();
+        init.code(code).bubbles(true);
+        let event = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+        self.find("input").dispatch_event(&event).unwrap();
+    }
+
+    fn mousedown(&self, test_id: &str) {
+        let mut init = MouseEventInit::new();
+        init.bubbles(true).cancelable(true);
+        let event = MouseEvent::new_with_mouse_event_init_dict("mousedown", &init).unwrap();
+        // Items listen on their inner `<p>`
+        let item = self.find(test_id);
+        item.first_element_child()
+            .unwrap()
+            .dispatch_event(&event)
+            .unwrap();
+    }
+}
+
+fn mount(state: SelectState<String>) -> Mounted {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    let selected = Rc::new(RefCell::new(Vec::new()));
+    let onselected = {
+        let selected = selected.clone();
+        let state = state.clone();
+        Callback::from(move |index| {
+            state.select(index);
+            selected.borrow_mut().push(index);
+        })
+    };
+
+    let props = yew::props!(SelectProps<String> {
+        state,
+        display: fixtures::display(),
+        onselected: Some(onselected),
+        test_id: Some(String::from("select")),
+    });
+    App::<Select<String>>::new().mount_with_props(root.clone(), props);
+
+    Mounted { root, selected }
+}
+
+#[wasm_bindgen_test]
+fn arrow_keys_move_highlight() {
+    let select = mount(fixtures::state(5, Selection::none()));
+
+    select.find("input").focus().unwrap();
+    select.key("ArrowDown");
+    select.key("ArrowDown");
+    select.key("ArrowUp");
+
+    assert!(select.is_open());
+    assert!(select.find("item-1").class_list().contains("is-active"));
+    assert!(!select.find("item-0").class_list().contains("is-active"));
+}
+
+#[wasm_bindgen_test]
+fn enter_selects_highlighted() {
+    let select = mount(fixtures::state(5, Selection::none()));
+
+    select.find("input").focus().unwrap();
+    select.key("ArrowDown");
+    select.key("Enter");
+
+    assert_eq!(*select.selected.borrow(), vec![1]);
+}
+
+#[wasm_bindgen_test]
+fn select_closes_menu() {
+    let select = mount(fixtures::state(5, Selection::none()));
+
+    select.find("input").focus().unwrap();
+    assert!(select.is_open());
+
+    select.mousedown("item-3");
+    assert_eq!(*select.selected.borrow(), vec![3]);
+    assert!(!select.is_open());
+}
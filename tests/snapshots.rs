@@ -0,0 +1,212 @@
+//! Markup snapshot tests, so changes to the rendered structure (and ARIA
+//! attributes) show up in review. Run with
+//! `wasm-pack test --headless --firefox -- --features testing --test snapshots`
+//!
+//! Each case renders a select (focused, so the menu is open) and compares its
+//! markup with `tests/snapshots/<name>.html`. Snapshots are only ever recorded
+//! from a run, never written by hand: an empty file hasn't been recorded yet,
+//! and the failure prints the rendered markup to paste into it (as it does when
+//! the markup changes on purpose).
+
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::{Element, HtmlElement};
+use yew::App;
+
+use yew_bulma_search_select::{fixtures, Select, SelectGroup, SelectProps, SelectState, Selection};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Render `props` into a root of its own, and open its menu. The root is the
+/// only one in the page (see `assert_snapshot`), so the menu is placed the same
+/// way in every case.
+fn render(props: SelectProps<String>) -> Element {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    App::<Select<String>>::new().mount_with_props(root.clone(), props);
+    root.query_selector("[data-testid=\"select-input\"]")
+        .unwrap()
+        .expect("missing input")
+        .unchecked_into::<HtmlElement>()
+        .focus()
+        .unwrap();
+    root
+}
+
+/// Markup with generated ids (`ybss-<kind>-<n>`) numbered from 0 in order of
+/// appearance, attributes sorted, and one tag per line, so snapshots don't
+/// depend on test order (or the order attributes were set in) and diff readably.
+fn normalize(markup: &str) -> String {
+    let mut ids: Vec<&str> = Vec::new();
+    let mut parts = markup.split("ybss-");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        out.push_str("ybss-");
+        // A generated id continues as `<kind>-<n>`, anything else (e.g. a class) is kept
+        let id = part.find('-').and_then(|dash| {
+            let digits = part[dash + 1..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            if dash > 0 && digits > 0 {
+                Some((dash, dash + 1 + digits))
+            } else {
+                None
+            }
+        });
+        match id {
+            Some((dash, len)) => {
+                let n = ids
+                    .iter()
+                    .position(|seen| *seen == &part[..len])
+                    .unwrap_or_else(|| {
+                        ids.push(&part[..len]);
+                        ids.len() - 1
+                    });
+                out.push_str(&format!("{}-{}", &part[..dash], n));
+                out.push_str(&part[len..]);
+            }
+            None => out.push_str(part),
+        }
+    }
+    sort_attributes(&out).replace("><", ">\n<")
+}
+
+/// Sort the attributes of each start tag by name, dropping empty `class`es
+/// (which only depend on how an empty class list is rendered)
+fn sort_attributes(markup: &str) -> String {
+    let mut out = String::with_capacity(markup.len());
+    let mut rest = markup;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        // Serialized attribute values are always double quoted
+        let mut quoted = false;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                if c == '"' {
+                    quoted = !quoted;
+                }
+                c == '>' && !quoted
+            })
+            .map_or(rest.len(), |(end, _)| end + 1);
+        let tag = &rest[..end];
+        rest = &rest[end..];
+
+        let inner = tag.trim_start_matches('<').trim_end_matches('>');
+        let name_end = match inner.find(' ') {
+            Some(name_end) if !inner.starts_with('/') => name_end,
+            _ => {
+                out.push_str(tag);
+                continue;
+            }
+        };
+        let mut attributes = Vec::new();
+        let mut remaining = inner[name_end..].trim_start();
+        while !remaining.is_empty() {
+            let len = match remaining.find("=\"") {
+                Some(eq) => {
+                    eq + 2
+                        + remaining[eq + 2..]
+                            .find('"')
+                            .map_or(remaining.len() - eq - 2, |quote| quote + 1)
+                }
+                None => remaining.find(' ').unwrap_or(remaining.len()),
+            };
+            attributes.push(&remaining[..len]);
+            remaining = remaining[len..].trim_start();
+        }
+        attributes.retain(|attribute| *attribute != "class=\"\"");
+        attributes.sort_unstable();
+
+        out.push('<');
+        out.push_str(&inner[..name_end]);
+        for attribute in attributes {
+            out.push(' ');
+            out.push_str(attribute);
+        }
+        out.push('>');
+    }
+    out.push_str(rest);
+    out
+}
+
+fn assert_snapshot(name: &str, snapshot: &str, root: &Element) {
+    let actual = normalize(&root.inner_html());
+    root.remove();
+    if snapshot.trim().is_empty() {
+        panic!(
+            "no snapshot recorded for `{}`, save this to tests/snapshots/{}.html:\n{}\n",
+            name, name, actual
+        );
+    }
+    if actual.trim() != snapshot.trim() {
+        panic!(
+            "markup of `{}` doesn't match tests/snapshots/{}.html, update it to:\n{}\n",
+            name, name, actual
+        );
+    }
+}
+
+macro_rules! snapshot {
+    ($name:ident, $props:expr) => {
+        #[wasm_bindgen_test]
+        fn $name() {
+            let root = render($props);
+            assert_snapshot(
+                stringify!($name),
+                include_str!(concat!("snapshots/", stringify!($name), ".html")),
+                &root,
+            );
+        }
+    };
+}
+
+fn props(state: SelectState<String>) -> SelectProps<String> {
+    yew::props!(SelectProps<String> {
+        state,
+        display: fixtures::display(),
+        test_id: Some(String::from("select")),
+    })
+}
+
+snapshot!(single, props(fixtures::state(3, Selection::some(1))));
+
+snapshot!(
+    multiple,
+    props(fixtures::state(3, Selection::multiple(vec![0, 2])))
+);
+
+snapshot!(
+    omit_selected,
+    SelectProps {
+        omit_selected: true,
+        ..props(fixtures::state(3, Selection::multiple(vec![1])))
+    }
+);
+
+snapshot!(empty, props(fixtures::state(0, Selection::none())));
+
+snapshot!(
+    loading,
+    SelectProps {
+        loading: true,
+        ..props(fixtures::state(3, Selection::none()))
+    }
+);
+
+snapshot!(
+    grouped,
+    SelectProps {
+        group_by: Some(SelectGroup::new(|item: &String| {
+            let odd = item.ends_with(|c: char| c.to_digit(10).map_or(false, |d| d % 2 == 1));
+            String::from(if odd { "Odd" } else { "Even" })
+        })),
+        ..props(fixtures::state(4, Selection::none()))
+    }
+);